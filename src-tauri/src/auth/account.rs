@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use anyhow::Result;
+use serde::{Deserialize, Deserializer, Serialize};
 use specta::Type;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
@@ -17,38 +18,239 @@ pub struct Cape {
     alias: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Type)]
 #[serde(rename_all = "camelCase")]
-pub struct Account {
-    pub active: bool,
-    #[specta(type = String)]
-    pub expiry: u64,
+pub enum AccountKind {
+    Microsoft,
+    Offline,
+    Yggdrasil,
+}
+
+// Credentials never leave the backend, they live in the credential store and are only
+// loaded to refresh a session or launch the game.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MicrosoftCredentials {
     pub access_token: String,
     pub refresh_token: String,
+    pub expiry: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct YggdrasilCredentials {
+    pub api_root: String,
+    pub access_token: String,
+    pub client_token: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Credentials {
+    Microsoft(MicrosoftCredentials),
+    Offline,
+    Yggdrasil(YggdrasilCredentials),
+}
+
+impl Credentials {
+    pub fn kind(&self) -> AccountKind {
+        match self {
+            Credentials::Microsoft(_) => AccountKind::Microsoft,
+            Credentials::Offline => AccountKind::Offline,
+            Credentials::Yggdrasil(_) => AccountKind::Yggdrasil,
+        }
+    }
+
+    // Unix timestamp of the access token expiry, for tokens that expire on a schedule.
+    pub fn expiry(&self) -> Option<u64> {
+        match self {
+            Credentials::Microsoft(credentials) => Some(credentials.expiry),
+            Credentials::Offline | Credentials::Yggdrasil(_) => None,
+        }
+    }
+
+    pub fn access_token(&self) -> Option<&str> {
+        match self {
+            Credentials::Microsoft(credentials) => Some(&credentials.access_token),
+            Credentials::Offline => None,
+            Credentials::Yggdrasil(credentials) => Some(&credentials.access_token),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Account {
+    pub active: bool,
     pub profile: Profile,
+    pub kind: AccountKind,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenStatus {
+    Valid,
+    Expired,
+    NeedsReauth,
+    Unknown,
 }
 
-impl Default for Account {
-    fn default() -> Self {
-        Account {
-            active: false,
-            expiry: 0,
-            access_token: String::new(),
-            refresh_token: String::new(),
-            profile: Profile {
-                id: String::new(),
-                name: String::new(),
-                skins: Vec::new(),
-                capes: Vec::new(),
+// The only account type sent to the frontend, credentials never leave the backend.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSummary {
+    pub id: String,
+    pub name: String,
+    pub kind: AccountKind,
+    pub skin: Option<String>,
+    pub status: TokenStatus,
+    pub active: bool,
+}
+
+impl AccountSummary {
+    pub fn new(account: &Account, status: TokenStatus) -> Self {
+        AccountSummary {
+            id: account.profile.id.clone(),
+            name: account.profile.name.clone(),
+            kind: account.kind,
+            skin: account.profile.active_skin().map(str::to_string),
+            status,
+            active: account.active,
+        }
+    }
+}
+
+// An account together with its credentials, as handled by the auth providers.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub account: Account,
+    pub credentials: Credentials,
+}
+
+impl Session {
+    pub fn new(active: bool, profile: Profile, credentials: Credentials) -> Self {
+        Session {
+            account: Account {
+                active,
+                profile,
+                kind: credentials.kind(),
             },
+            credentials,
+        }
+    }
+}
+
+// Accounts carried their credentials in the config before the credential store, and
+// before that were flat Microsoft records, with an empty placeholder account standing
+// in for "no account".
+#[derive(Deserialize)]
+struct AccountWithCredentials {
+    active: bool,
+    profile: Profile,
+    credentials: Credentials,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LegacyAccount {
+    active: bool,
+    expiry: u64,
+    access_token: String,
+    refresh_token: String,
+    profile: Profile,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredAccount {
+    Current(Account),
+    WithCredentials(AccountWithCredentials),
+    Legacy(LegacyAccount),
+}
+
+impl StoredAccount {
+    fn into_parts(self) -> Option<(Account, Option<Credentials>)> {
+        match self {
+            StoredAccount::Current(account) => Some((account, None)),
+            StoredAccount::WithCredentials(account) => {
+                let session = Session::new(account.active, account.profile, account.credentials);
+                Some((session.account, Some(session.credentials)))
+            }
+            StoredAccount::Legacy(legacy) if legacy.profile.id.is_empty() => None,
+            StoredAccount::Legacy(legacy) => {
+                let credentials = if legacy.profile.offline {
+                    Credentials::Offline
+                } else {
+                    Credentials::Microsoft(MicrosoftCredentials {
+                        access_token: legacy.access_token,
+                        refresh_token: legacy.refresh_token,
+                        expiry: legacy.expiry,
+                    })
+                };
+                let session = Session::new(legacy.active, legacy.profile, credentials);
+                Some((session.account, Some(session.credentials)))
+            }
         }
     }
 }
 
+pub fn deserialize_accounts<'de, D>(deserializer: D) -> Result<Vec<Account>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let accounts = Vec::<StoredAccount>::deserialize(deserializer)?
+        .into_iter()
+        .filter_map(StoredAccount::into_parts)
+        .map(|(account, _)| account)
+        .collect();
+
+    Ok(accounts)
+}
+
+// Credentials still stored in an older config, keyed by profile ID.
+pub fn stored_credentials(accounts: serde_json::Value) -> Result<Vec<(String, Credentials)>> {
+    let credentials = Vec::<StoredAccount>::deserialize(accounts)?
+        .into_iter()
+        .filter_map(StoredAccount::into_parts)
+        .filter_map(|(account, credentials)| Some((account.profile.id, credentials?)))
+        .collect();
+
+    Ok(credentials)
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
 pub struct Profile {
     pub id: String,
     pub name: String,
     pub skins: Vec<Skin>,
     pub capes: Vec<Cape>,
+    #[serde(default)]
+    pub offline: bool,
+}
+
+impl Profile {
+    pub fn active_skin(&self) -> Option<&str> {
+        self.skins
+            .iter()
+            .find(|skin| skin.state == "ACTIVE")
+            .map(|skin| skin.url.as_str())
+    }
+
+    pub fn offline(name: &str) -> Self {
+        Profile {
+            id: offline_uuid(name),
+            name: name.to_string(),
+            skins: Vec::new(),
+            capes: Vec::new(),
+            offline: true,
+        }
+    }
+}
+
+// Same as the server's `UUID.nameUUIDFromBytes("OfflinePlayer:" + name)`, so offline
+// players keep their data when a world is opened to LAN or moved to an offline server.
+fn offline_uuid(name: &str) -> String {
+    let mut bytes = md5::compute(format!("OfflinePlayer:{}", name)).0;
+    bytes[6] = (bytes[6] & 0x0f) | 0x30;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
 }
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{
         Arc,
         atomic::{AtomicBool, Ordering},
@@ -7,27 +7,42 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use anyhow::{Error, Ok, Result};
-use log::info;
+use anyhow::{Error, Result, anyhow};
+use log::{error, info, warn};
+use once_cell::sync::Lazy;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
 use specta::Type;
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State};
 use tauri_specta::Event;
-use tokio::time::sleep;
+use tokio::{sync::Mutex, time::sleep};
 
-use crate::{AppState, auth::account::Profile, config};
+use crate::{AppState, config};
 
 use super::{
-    account::Account,
-    structs::{
-        AuthorizationTokenResponse, DeviceCodeResponse, MinecraftAuthenticationResponse,
-        MinecraftProfileResponse, RefreshTokenResponse, XboxLiveAuthenticationResponse,
-    },
+    account::{self, Account, AccountKind, AccountSummary, Credentials, Session, TokenStatus},
+    credential_store,
+    provider::{self, AuthContext, CredentialsRejected, LoginRequest},
 };
 
-const CLIENT_ID: &str = "04bc8538-fc3c-4490-9e61-a2b3f4cbcf5c";
+// Tokens are refreshed this long before they expire, so a game started right before
+// the expiry still gets a usable token.
+const REFRESH_MARGIN: Duration = Duration::from_secs(15 * 60);
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+const MAX_REFRESH_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+// Refreshes rotate tokens, running two at once for the same account would leave one
+// of them holding a stale refresh token. Other accounts are not held up by a slow one.
+static REFRESH_LOCKS: Lazy<std::sync::Mutex<HashMap<String, Arc<Mutex<()>>>>> =
+    Lazy::new(|| std::sync::Mutex::new(HashMap::new()));
+// Set once no tokens are left in the config, until then `config::save_config` writes
+// the accounts back exactly as they were read.
+static CREDENTIALS_MIGRATED: AtomicBool = AtomicBool::new(false);
+static MIGRATION_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+// Accounts whose credentials were rejected, skipped by the background refresh until
+// the user logs in again.
+static NEEDS_REAUTH: Lazy<std::sync::Mutex<HashSet<String>>> =
+    Lazy::new(|| std::sync::Mutex::new(HashSet::new()));
 
 #[derive(Clone)]
 pub struct LoginHandle {
@@ -52,145 +67,294 @@ pub struct LoginDetailsEvent<'a> {
     pub uri: &'a str,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Type, Event)]
+pub struct AccountNeedsReauthEvent<'a> {
+    pub id: &'a str,
+    pub name: &'a str,
+    pub reason: &'a str,
+}
+
 pub async fn login(
     state: &State<'_, AppState>,
     handle: AppHandle,
     login_handle: LoginHandle,
-) -> Result<MinecraftProfileResponse, Error> {
+    request: LoginRequest,
+) -> Result<AccountSummary, Error> {
     let client = state.client.lock().await;
+    let context = AuthContext {
+        client: &client,
+        handle: &handle,
+        login_handle: &login_handle,
+    };
 
-    let device_code_response = device_response(&client).await?;
-    let device_response = device_code_response;
+    migrate_credentials().await?;
+    let session = provider::login(&context, request).await?;
+    let summary = AccountSummary::new(&session.account, TokenStatus::Valid);
+    add_account(session).await?;
 
-    LoginDetailsEvent {
-        code: &device_response.user_code,
-        uri: &device_response.verification_uri,
-    }
-    .emit(&handle)?;
+    Ok(summary)
+}
 
-    let mut authentication_response: Option<AuthorizationTokenResponse> = None;
-    while authentication_response.is_none() {
-        if login_handle.cancel.load(Ordering::SeqCst) {
-            return Err(Error::msg("Login cancelled"));
-        }
+async fn add_account(session: Session) -> Result<(), Error> {
+    save_credentials(&session).await?;
 
-        match authorization_token_response(&device_response.device_code, &client).await {
-            Result::Ok(token_response) => {
-                authentication_response = Some(token_response);
-                info!("Received authentication token");
-            }
-            Err(e) => {
-                info!("Failed to receive authentication token: {}", e);
-                sleep(Duration::from_secs(device_response.interval)).await;
+    let mut config = config::get_config()?;
+    let accounts = &mut config.accounts;
+    let account = session.account;
+
+    accounts.iter_mut().for_each(|acc| acc.active = false);
+    accounts.retain(|acc| acc.profile.id != account.profile.id);
+    NEEDS_REAUTH.lock().unwrap().remove(&account.profile.id);
+    info!("Added {:?} account {}", account.kind, account.profile.name);
+    accounts.push(account);
+
+    config::save_config(&config)?;
+    Ok(())
+}
+
+async fn update_account(session: Session) -> Result<(), Error> {
+    save_credentials(&session).await?;
+
+    let mut config = config::get_config()?;
+    let account = session.account;
+    match config
+        .accounts
+        .iter_mut()
+        .find(|acc| acc.profile.id == account.profile.id)
+    {
+        // Keep the active flag, the user may have switched accounts during a refresh.
+        Some(existing) => {
+            *existing = Account {
+                active: existing.active,
+                ..account
             }
         }
+        None => return Err(anyhow!("Account {} not found", account.profile.name)),
     }
 
-    let auth_response = authentication_response.unwrap();
-    let xbox_response = xbox_response(&auth_response.access_token, &client).await?;
-    let xbox_security_token_response =
-        xbox_security_token_response(xbox_response.token, &client).await?;
-    let minecraft_response = minecraft_response(
-        xbox_security_token_response.display_claims,
-        xbox_security_token_response.token,
-        &client,
-    )
-    .await?;
-    let minecraft_profile_response =
-        minecraft_profile_response(&minecraft_response.access_token, &client).await?;
-
-    let minecraft_profile_response_clone = minecraft_profile_response.clone();
-
-    let expires_in = Duration::from_secs(auth_response.expires_in);
-    let system_time = SystemTime::now().duration_since(UNIX_EPOCH)?;
-    let combined_duration = system_time + expires_in;
-    let combined_timestamp = combined_duration.as_secs();
-
-    let account = Account {
-        active: true,
-        expiry: combined_timestamp,
-        access_token: minecraft_response.access_token,
-        refresh_token: auth_response.refresh_token,
-        profile: minecraft_profile_response_clone.into(),
-    };
-
-    let mut config = config::get_config()?;
-    let accounts = &mut config.accounts;
-    let default_account = Account::default();
+    config::save_config(&config)?;
+    Ok(())
+}
 
-    if accounts.is_empty() {
-        accounts.push(account);
-    } else {
-        accounts.iter_mut().for_each(|acc| acc.active = false);
-        accounts.retain(|acc| acc != &default_account);
-        accounts.push(account);
+async fn save_credentials(session: &Session) -> Result<(), Error> {
+    match session.credentials {
+        Credentials::Offline => Ok(()),
+        _ => credential_store::save(&session.account.profile.id, &session.credentials).await,
     }
+}
 
-    config::save_config(&config)?;
+async fn load_session(account: Account) -> Result<Session, Error> {
+    let credentials = match account.kind {
+        AccountKind::Offline => Credentials::Offline,
+        _ => credential_store::load(&account.profile.id)
+            .await?
+            .ok_or_else(|| {
+                CredentialsRejected(format!(
+                    "No stored credentials for account {}",
+                    account.profile.name
+                ))
+            })?,
+    };
 
-    Ok(minecraft_profile_response)
+    Ok(Session {
+        account,
+        credentials,
+    })
 }
 
-pub async fn refresh(client: &Client) -> Result<(), Error> {
+pub async fn refresh(client: &Client, handle: &AppHandle) -> Result<(), Error> {
+    migrate_credentials().await?;
     let config = config::get_config()?;
-    let default_account = Account::default();
-    if config.accounts.len() == 1 && config.accounts[0] == default_account {
+    if config.accounts.is_empty() {
         info!("No accounts to refresh");
         return Ok(());
     }
+
     for account in config.accounts {
-        let expiry = Duration::from_secs(account.expiry);
-        let system_time = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        if NEEDS_REAUTH.lock().unwrap().contains(&account.profile.id) {
+            continue;
+        }
 
-        if expiry <= system_time {
-            info!("Refreshing token for account: {}", account.profile.name);
+        // A failing account must not keep the others from being refreshed.
+        if let Err(e) = refresh_account(client, handle, &account).await {
+            error!(
+                "Failed to refresh token for account {}: {}",
+                account.profile.name, e
+            );
+        }
+    }
 
-            let refresh_token_response =
-                refresh_token_response(&account.refresh_token, client).await?;
-            let xbox_response = xbox_response(&refresh_token_response.access_token, client).await?;
-            let xbox_security_token_response =
-                xbox_security_token_response(xbox_response.token, client).await?;
-            let minecraft_response = minecraft_response(
-                xbox_security_token_response.display_claims,
-                xbox_security_token_response.token,
-                client,
-            )
-            .await?;
-            let minecraft_profile_response =
-                minecraft_profile_response(&minecraft_response.access_token, client).await?;
-
-            let expires_in = Duration::from_secs(refresh_token_response.expires_in.into());
-            let system_time = SystemTime::now().duration_since(UNIX_EPOCH)?;
-            let combined_duration = system_time + expires_in;
-            let combined_timestamp = combined_duration.as_secs();
-
-            let profile: Profile = minecraft_profile_response.clone().into();
-
-            let new_account = Account {
-                active: account.active,
-                expiry: combined_timestamp,
-                access_token: minecraft_response.access_token,
-                refresh_token: refresh_token_response.refresh_token,
-                profile,
-            };
-
-            let mut config = config::get_config()?;
-            config
-                .accounts
-                .retain(|acc| acc.profile.id != new_account.profile.id);
-            config.accounts.push(new_account);
-            config::save_config(&config)?;
+    Ok(())
+}
 
-            info!("Token refreshed for account: {}", account.profile.name);
-        } else {
+async fn refresh_account(
+    client: &Client,
+    handle: &AppHandle,
+    account: &Account,
+) -> Result<Session, Error> {
+    let lock = REFRESH_LOCKS
+        .lock()
+        .unwrap()
+        .entry(account.profile.id.clone())
+        .or_default()
+        .clone();
+    let _guard = lock.lock().await;
+
+    // Another refresh may have finished while waiting for the lock.
+    let result = match load_session(account.clone()).await {
+        Ok(session) if !provider::needs_refresh(&session, REFRESH_MARGIN) => {
             info!("Token for account: {} is still valid", account.profile.name);
+            return Ok(session);
+        }
+        Ok(session) => {
+            info!("Refreshing token for account: {}", account.profile.name);
+            provider::refresh(client, &session).await
+        }
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(refreshed) => {
+            update_account(refreshed.clone()).await?;
+            info!("Token refreshed for account: {}", account.profile.name);
+            Ok(refreshed)
+        }
+        Err(e) => {
+            if let Some(rejected) = e.downcast_ref::<CredentialsRejected>() {
+                warn!(
+                    "Credentials for account {} were rejected, a new login is required",
+                    account.profile.name
+                );
+                NEEDS_REAUTH
+                    .lock()
+                    .unwrap()
+                    .insert(account.profile.id.clone());
+                AccountNeedsReauthEvent {
+                    id: &account.profile.id,
+                    name: &account.profile.name,
+                    reason: &rejected.0,
+                }
+                .emit(handle)?;
+            }
+            Err(e)
+        }
+    }
+}
+
+// Returns the active account with a token that stays valid for at least the refresh
+// margin, for handing to the game. When the auth server cannot be reached the stored
+// token is used as long as it has not expired yet.
+pub async fn get_launch_session(client: &Client, handle: &AppHandle) -> Result<Session, Error> {
+    migrate_credentials().await?;
+    let account = get_active_account()?.ok_or_else(|| anyhow!("No active account found"))?;
+    let error = match refresh_account(client, handle, &account).await {
+        Ok(session) => return Ok(session),
+        Err(e) if e.downcast_ref::<CredentialsRejected>().is_some() => return Err(e),
+        Err(e) => e,
+    };
+
+    match load_session(account).await {
+        Ok(session) if !is_expired(&session.credentials) => {
+            warn!(
+                "Failed to refresh token for account {}, launching with the stored one: {}",
+                session.account.profile.name, error
+            );
+            Ok(session)
+        }
+        _ => Err(error),
+    }
+}
+
+fn is_expired(credentials: &Credentials) -> bool {
+    credentials.expiry().is_some_and(|expiry| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .is_ok_and(|now| Duration::from_secs(expiry) <= now)
+    })
+}
+
+pub async fn run_refresh_task(handle: AppHandle) {
+    loop {
+        let client = handle.state::<AppState>().client.lock().await.clone();
+        if let Err(e) = refresh(&client, &handle).await {
+            error!("Failed to refresh accounts: {}", e);
+        }
+
+        sleep(next_refresh_delay().await).await;
+    }
+}
+
+async fn next_refresh_delay() -> Duration {
+    let Ok(config) = config::get_config() else {
+        return MAX_REFRESH_INTERVAL;
+    };
+    let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) else {
+        return MAX_REFRESH_INTERVAL;
+    };
+
+    let mut delay = MAX_REFRESH_INTERVAL;
+    for account in config.accounts {
+        if NEEDS_REAUTH.lock().unwrap().contains(&account.profile.id) {
+            continue;
+        }
+
+        let expiry = match credential_store::load(&account.profile.id).await {
+            Ok(Some(credentials)) => credentials.expiry(),
+            _ => None,
+        };
+        if let Some(expiry) = expiry {
+            let until_refresh = Duration::from_secs(expiry)
+                .saturating_sub(REFRESH_MARGIN)
+                .saturating_sub(now);
+            delay = delay.min(until_refresh);
         }
     }
 
+    delay.max(MIN_REFRESH_INTERVAL)
+}
+
+pub fn credentials_migrated() -> bool {
+    CREDENTIALS_MIGRATED.load(Ordering::SeqCst)
+}
+
+// Account tokens used to be stored in the config, they are moved to the credential
+// store before the config is written back without them. Started in the background at
+// startup, everything reading or writing accounts awaits it first, which also retries
+// a migration that failed.
+pub async fn migrate_credentials() -> Result<(), Error> {
+    let _guard = MIGRATION_LOCK.lock().await;
+    if credentials_migrated() {
+        return Ok(());
+    }
+
+    let credentials = match config::get_raw_accounts()? {
+        Some(accounts) => account::stored_credentials(accounts)?,
+        None => Vec::new(),
+    };
+    if credentials.is_empty() {
+        CREDENTIALS_MIGRATED.store(true, Ordering::SeqCst);
+        return Ok(());
+    }
+
+    for (id, credentials) in &credentials {
+        if *credentials != Credentials::Offline {
+            credential_store::save(id, credentials).await?;
+        }
+    }
+
+    CREDENTIALS_MIGRATED.store(true, Ordering::SeqCst);
+    let config = config::get_config()?;
+    config::save_config(&config)?;
+    info!(
+        "Moved credentials of {} accounts to the credential store",
+        credentials.len()
+    );
+
     Ok(())
 }
 
-pub fn switch_account(id: String) -> Result<(), Error> {
+pub async fn switch_account(id: String) -> Result<(), Error> {
+    migrate_credentials().await?;
     let mut config = config::get_config()?;
     config
         .accounts
@@ -206,7 +370,8 @@ pub fn switch_account(id: String) -> Result<(), Error> {
     }
 }
 
-pub fn delete_account(id: String) -> Result<(), Error> {
+pub async fn delete_account(client: &Client, id: String) -> Result<(), Error> {
+    migrate_credentials().await?;
     let mut config = config::get_config()?;
 
     let mut deleted_account_was_active = false;
@@ -216,9 +381,32 @@ pub fn delete_account(id: String) -> Result<(), Error> {
         if account_to_delete.active {
             deleted_account_was_active = true;
         }
+
+        // Revoking the session is best effort, the account is removed either way.
+        let logout = match load_session(account_to_delete.clone()).await {
+            Ok(session) => provider::logout(client, &session).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = logout {
+            warn!(
+                "Failed to sign out of account {}: {}",
+                account_to_delete.profile.name, e
+            );
+        }
+
+        if account_to_delete.kind != AccountKind::Offline {
+            if let Err(e) = credential_store::delete(&id).await {
+                warn!(
+                    "Failed to remove stored credentials of account {}: {}",
+                    account_to_delete.profile.name, e
+                );
+            }
+        }
     }
 
     config.accounts.retain(|acc| acc.profile.id != id);
+    NEEDS_REAUTH.lock().unwrap().remove(&id);
+    REFRESH_LOCKS.lock().unwrap().remove(&id);
 
     if deleted_account_was_active && !config.accounts.is_empty() {
         let is_another_account_active = config.accounts.iter().any(|acc| acc.active);
@@ -249,133 +437,43 @@ pub fn get_active_account() -> Result<Option<Account>> {
     Ok(config.accounts.into_iter().find(|acc| acc.active))
 }
 
-async fn device_response(client: &Client) -> Result<DeviceCodeResponse> {
-    let response = client
-        .get("https://login.microsoftonline.com/consumers/oauth2/v2.0/devicecode")
-        .form(&vec![
-            ("client_id", CLIENT_ID),
-            ("response_type", "code"),
-            ("scope", "XboxLive.signin offline_access"),
-        ])
-        .send()
-        .await?
-        .json::<DeviceCodeResponse>()
-        .await?;
-
-    Ok(response)
-}
-
-async fn authorization_token_response(
-    device_code: &str,
-    client: &Client,
-) -> Result<AuthorizationTokenResponse> {
-    let response = client
-        .post("https://login.microsoftonline.com/consumers/oauth2/v2.0/token")
-        .form(&vec![
-            ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
-            ("client_id", CLIENT_ID),
-            ("device_code", device_code),
-        ])
-        .send()
-        .await?
-        .json::<AuthorizationTokenResponse>()
-        .await?;
-
-    Ok(response)
-}
-
-async fn refresh_token_response(
-    refresh_token: &str,
-    client: &Client,
-) -> Result<RefreshTokenResponse> {
-    let response = client
-        .post("https://login.microsoftonline.com/consumers/oauth2/v2.0/token")
-        .form(&vec![
-            ("grant_type", "refresh_token"),
-            ("client_id", CLIENT_ID),
-            ("refresh_token", refresh_token),
-        ])
-        .send()
-        .await?
-        .json::<RefreshTokenResponse>()
-        .await?;
-    Ok(response)
+pub async fn get_account_summaries() -> Result<Vec<AccountSummary>, Error> {
+    migrate_credentials().await?;
+    let mut summaries = Vec::new();
+    for account in config::get_config()?.accounts {
+        summaries.push(summarize(&account).await);
+    }
+    Ok(summaries)
 }
 
-async fn xbox_response(
-    access_token: &str,
-    client: &Client,
-) -> Result<XboxLiveAuthenticationResponse> {
-    let response = client
-        .post("https://user.auth.xboxlive.com/user/authenticate")
-        .json(&json!({
-                "Properties": {
-                "AuthMethod": "RPS",
-                "SiteName": "user.auth.xboxlive.com",
-                "RpsTicket": &format!("d={}", access_token)
-            },
-            "RelyingParty": "http://auth.xboxlive.com",
-            "TokenType": "JWT"
-        }))
-        .send()
-        .await?
-        .json::<XboxLiveAuthenticationResponse>()
-        .await?;
-
-    Ok(response)
+pub async fn get_active_account_summary() -> Result<Option<AccountSummary>, Error> {
+    migrate_credentials().await?;
+    match get_active_account()? {
+        Some(account) => Ok(Some(summarize(&account).await)),
+        None => Ok(None),
+    }
 }
 
-async fn xbox_security_token_response(
-    token: String,
-    client: &Client,
-) -> Result<XboxLiveAuthenticationResponse> {
-    let response = client
-        .post("https://xsts.auth.xboxlive.com/xsts/authorize")
-        .json(&json!({
-            "Properties": {
-                "SandboxId": "RETAIL",
-                "UserTokens": [&token]
-            },
-            "RelyingParty": "rp://api.minecraftservices.com/",
-            "TokenType": "JWT"
-        }))
-        .send()
-        .await?
-        .json::<XboxLiveAuthenticationResponse>()
-        .await?;
-
-    Ok(response)
-}
+async fn summarize(account: &Account) -> AccountSummary {
+    if NEEDS_REAUTH.lock().unwrap().contains(&account.profile.id) {
+        return AccountSummary::new(account, TokenStatus::NeedsReauth);
+    }
+    if account.kind == AccountKind::Offline {
+        return AccountSummary::new(account, TokenStatus::Valid);
+    }
 
-async fn minecraft_response(
-    display_claims: HashMap<String, Vec<HashMap<String, String>>>,
-    token: String,
-    client: &Client,
-) -> Result<MinecraftAuthenticationResponse, Error> {
-    let response = client
-        .post("https://api.minecraftservices.com/authentication/login_with_xbox")
-        .json(&json!({
-            "identityToken": &format!("XBL3.0 x={};{}", &display_claims["xui"][0]["uhs"], token)
-        }))
-        .send()
-        .await?
-        .json::<MinecraftAuthenticationResponse>()
-        .await?;
-
-    Ok(response)
-}
+    let status = match credential_store::load(&account.profile.id).await {
+        Ok(Some(credentials)) if is_expired(&credentials) => TokenStatus::Expired,
+        Ok(Some(_)) => TokenStatus::Valid,
+        Ok(None) => TokenStatus::NeedsReauth,
+        Err(e) => {
+            warn!(
+                "Failed to read credentials of account {}: {}",
+                account.profile.name, e
+            );
+            TokenStatus::Unknown
+        }
+    };
 
-async fn minecraft_profile_response(
-    access_token: &String,
-    client: &Client,
-) -> Result<MinecraftProfileResponse, Error> {
-    let response = client
-        .get("https://api.minecraftservices.com/minecraft/profile")
-        .bearer_auth(access_token)
-        .send()
-        .await?
-        .json::<MinecraftProfileResponse>()
-        .await?;
-
-    Ok(response)
+    AccountSummary::new(account, status)
 }
@@ -0,0 +1,292 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::{Error, Result, anyhow};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use log::{info, warn};
+use ring::{
+    aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey},
+    hkdf::{HKDF_SHA256, Salt},
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    fs,
+    io::AsyncWriteExt,
+    sync::{Mutex, OnceCell},
+};
+
+use super::account::Credentials;
+use crate::config;
+
+const SALT_LEN: usize = 32;
+const KEY_INFO: &[u8] = b"glyph-launcher credentials v1";
+
+static STORE: OnceCell<CredentialStore> = OnceCell::const_new();
+
+pub trait CredentialBackend {
+    fn load(&self, id: &str) -> impl Future<Output = Result<Option<Credentials>, Error>> + Send;
+
+    fn save(
+        &self,
+        id: &str,
+        credentials: &Credentials,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    fn delete(&self, id: &str) -> impl Future<Output = Result<(), Error>> + Send;
+}
+
+pub enum CredentialStore {
+    #[cfg(target_os = "linux")]
+    SecretService(SecretServiceBackend),
+    EncryptedFile(EncryptedFileBackend),
+}
+
+async fn store() -> Result<&'static CredentialStore, Error> {
+    STORE
+        .get_or_try_init(|| async {
+            #[cfg(target_os = "linux")]
+            match SecretServiceBackend::connect().await {
+                Ok(backend) => {
+                    info!("Storing credentials in the Secret Service keyring");
+                    return Ok(CredentialStore::SecretService(backend));
+                }
+                Err(e) => warn!(
+                    "Secret Service is unavailable, falling back to an encrypted file: {}",
+                    e
+                ),
+            }
+
+            Ok(CredentialStore::EncryptedFile(EncryptedFileBackend::new()?))
+        })
+        .await
+}
+
+pub async fn load(id: &str) -> Result<Option<Credentials>, Error> {
+    match store().await? {
+        #[cfg(target_os = "linux")]
+        CredentialStore::SecretService(backend) => backend.load(id).await,
+        CredentialStore::EncryptedFile(backend) => backend.load(id).await,
+    }
+}
+
+pub async fn save(id: &str, credentials: &Credentials) -> Result<(), Error> {
+    match store().await? {
+        #[cfg(target_os = "linux")]
+        CredentialStore::SecretService(backend) => backend.save(id, credentials).await,
+        CredentialStore::EncryptedFile(backend) => backend.save(id, credentials).await,
+    }
+}
+
+pub async fn delete(id: &str) -> Result<(), Error> {
+    match store().await? {
+        #[cfg(target_os = "linux")]
+        CredentialStore::SecretService(backend) => backend.delete(id).await,
+        CredentialStore::EncryptedFile(backend) => backend.delete(id).await,
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub struct SecretServiceBackend {
+    service: secret_service::SecretService<'static>,
+}
+
+#[cfg(target_os = "linux")]
+impl SecretServiceBackend {
+    async fn connect() -> Result<Self, Error> {
+        use secret_service::{EncryptionType, SecretService};
+
+        let service = SecretService::connect(EncryptionType::Dh).await?;
+        // Fails when no keyring daemon provides a default collection.
+        service.get_default_collection().await?;
+        Ok(Self { service })
+    }
+
+    fn attributes(id: &str) -> HashMap<&str, &str> {
+        HashMap::from([("application", "glyph-launcher"), ("account", id)])
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl CredentialBackend for SecretServiceBackend {
+    async fn load(&self, id: &str) -> Result<Option<Credentials>, Error> {
+        let collection = self.service.get_default_collection().await?;
+        collection.ensure_unlocked().await?;
+
+        let items = collection.search_items(Self::attributes(id)).await?;
+        let Some(item) = items.first() else {
+            return Ok(None);
+        };
+
+        let secret = item.get_secret().await?;
+        Ok(Some(serde_json::from_slice(&secret)?))
+    }
+
+    async fn save(&self, id: &str, credentials: &Credentials) -> Result<(), Error> {
+        let collection = self.service.get_default_collection().await?;
+        collection.ensure_unlocked().await?;
+
+        collection
+            .create_item(
+                &format!("Glyph Launcher account {}", id),
+                Self::attributes(id),
+                &serde_json::to_vec(credentials)?,
+                true,
+                "application/json",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), Error> {
+        let collection = self.service.get_default_collection().await?;
+        collection.ensure_unlocked().await?;
+
+        for item in collection.search_items(Self::attributes(id)).await? {
+            item.delete().await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct EncryptedFile {
+    salt: String,
+    entries: HashMap<String, EncryptedEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EncryptedEntry {
+    nonce: String,
+    ciphertext: String,
+}
+
+// Credentials encrypted with AES-256-GCM under a key derived from the machine ID, so
+// the file is useless when copied to another machine. This is not protection against
+// other local users: the machine ID is world-readable (e.g. /etc/machine-id), so anyone
+// who can read the file can also decrypt it, which is why it is kept private to the
+// owner.
+pub struct EncryptedFileBackend {
+    path: PathBuf,
+    rng: SystemRandom,
+    // Serialises read-modify-write cycles on the file.
+    lock: Mutex<()>,
+}
+
+impl EncryptedFileBackend {
+    fn new() -> Result<Self, Error> {
+        Ok(Self {
+            path: config::get_config_dir()?.join("credentials.json"),
+            rng: SystemRandom::new(),
+            lock: Mutex::new(()),
+        })
+    }
+
+    async fn read(&self) -> Result<EncryptedFile, Error> {
+        if !fs::try_exists(&self.path).await? {
+            let mut salt = [0u8; SALT_LEN];
+            self.rng
+                .fill(&mut salt)
+                .map_err(|_| anyhow!("Failed to generate a salt"))?;
+            return Ok(EncryptedFile {
+                salt: STANDARD.encode(salt),
+                entries: HashMap::new(),
+            });
+        }
+
+        Ok(serde_json::from_str(
+            &fs::read_to_string(&self.path).await?,
+        )?)
+    }
+
+    async fn write(&self, file: &EncryptedFile) -> Result<(), Error> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let temp_path = self.path.with_extension("json.tmp");
+        // A leftover temp file would keep its permissions, the new one is created
+        // private so the credentials are never readable by other users.
+        if fs::try_exists(&temp_path).await? {
+            fs::remove_file(&temp_path).await?;
+        }
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut temp_file = options.open(&temp_path).await?;
+        temp_file
+            .write_all(serde_json::to_string_pretty(file)?.as_bytes())
+            .await?;
+        temp_file.sync_all().await?;
+        drop(temp_file);
+        fs::rename(&temp_path, &self.path).await?;
+        Ok(())
+    }
+
+    fn key(salt: &str) -> Result<LessSafeKey, Error> {
+        let machine_id =
+            machine_uid::get().map_err(|e| anyhow!("Failed to read the machine ID: {}", e))?;
+        let salt = STANDARD.decode(salt)?;
+
+        let prk = Salt::new(HKDF_SHA256, &salt).extract(machine_id.trim().as_bytes());
+        let okm = prk
+            .expand(&[KEY_INFO], &AES_256_GCM)
+            .map_err(|_| anyhow!("Failed to derive the credential key"))?;
+        Ok(LessSafeKey::new(UnboundKey::from(okm)))
+    }
+}
+
+impl CredentialBackend for EncryptedFileBackend {
+    async fn load(&self, id: &str) -> Result<Option<Credentials>, Error> {
+        let _guard = self.lock.lock().await;
+        let file = self.read().await?;
+        let Some(entry) = file.entries.get(id) else {
+            return Ok(None);
+        };
+
+        let nonce = Nonce::try_assume_unique_for_key(&STANDARD.decode(&entry.nonce)?)
+            .map_err(|_| anyhow!("Invalid nonce for account {}", id))?;
+        let mut data = STANDARD.decode(&entry.ciphertext)?;
+        let plaintext = Self::key(&file.salt)?
+            .open_in_place(nonce, Aad::from(id.as_bytes()), &mut data)
+            .map_err(|_| anyhow!("Failed to decrypt the credentials for account {}", id))?;
+
+        Ok(Some(serde_json::from_slice(plaintext)?))
+    }
+
+    async fn save(&self, id: &str, credentials: &Credentials) -> Result<(), Error> {
+        let _guard = self.lock.lock().await;
+        let mut file = self.read().await?;
+
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| anyhow!("Failed to generate a nonce"))?;
+        let mut data = serde_json::to_vec(credentials)?;
+        Self::key(&file.salt)?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(id.as_bytes()),
+                &mut data,
+            )
+            .map_err(|_| anyhow!("Failed to encrypt the credentials for account {}", id))?;
+
+        file.entries.insert(
+            id.to_string(),
+            EncryptedEntry {
+                nonce: STANDARD.encode(nonce),
+                ciphertext: STANDARD.encode(data),
+            },
+        );
+        self.write(&file).await
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), Error> {
+        let _guard = self.lock.lock().await;
+        let mut file = self.read().await?;
+        if file.entries.remove(id).is_some() {
+            self.write(&file).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,277 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::Ordering,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Error, Result, anyhow};
+use log::info;
+use reqwest::Client;
+use serde_json::json;
+use tauri_specta::Event;
+use tokio::time::sleep;
+
+use super::{
+    account::{Credentials, MicrosoftCredentials, Session},
+    auth::LoginDetailsEvent,
+    provider::{AuthContext, AuthProvider, CredentialsRejected},
+    structs::{
+        AuthorizationTokenResponse, DeviceCodeResponse, MinecraftAuthenticationResponse,
+        MinecraftProfileResponse, OAuthErrorResponse, RefreshTokenResponse,
+        XboxLiveAuthenticationResponse,
+    },
+};
+
+const CLIENT_ID: &str = "04bc8538-fc3c-4490-9e61-a2b3f4cbcf5c";
+
+pub struct MicrosoftProvider;
+
+impl AuthProvider for MicrosoftProvider {
+    type LoginRequest = ();
+
+    async fn login(&self, context: &AuthContext<'_>, _request: ()) -> Result<Session, Error> {
+        let client = context.client;
+
+        let device_response = device_response(client).await?;
+
+        LoginDetailsEvent {
+            code: &device_response.user_code,
+            uri: &device_response.verification_uri,
+        }
+        .emit(context.handle)?;
+
+        let mut authentication_response: Option<AuthorizationTokenResponse> = None;
+        while authentication_response.is_none() {
+            if context.login_handle.cancel.load(Ordering::SeqCst) {
+                return Err(Error::msg("Login cancelled"));
+            }
+
+            match authorization_token_response(&device_response.device_code, client).await {
+                Ok(token_response) => {
+                    authentication_response = Some(token_response);
+                    info!("Received authentication token");
+                }
+                Err(e) => {
+                    info!("Failed to receive authentication token: {}", e);
+                    sleep(Duration::from_secs(device_response.interval)).await;
+                }
+            }
+        }
+
+        let auth_response = authentication_response.unwrap();
+        let xbox_response = xbox_response(&auth_response.access_token, client).await?;
+        let xbox_security_token_response =
+            xbox_security_token_response(xbox_response.token, client).await?;
+        let minecraft_response = minecraft_response(
+            xbox_security_token_response.display_claims,
+            xbox_security_token_response.token,
+            client,
+        )
+        .await?;
+        let minecraft_profile_response =
+            minecraft_profile_response(&minecraft_response.access_token, client).await?;
+
+        Ok(Session::new(
+            true,
+            minecraft_profile_response.into(),
+            Credentials::Microsoft(MicrosoftCredentials {
+                access_token: minecraft_response.access_token,
+                refresh_token: auth_response.refresh_token,
+                expiry: expiry_from_now(minecraft_response.expires_in.into())?,
+            }),
+        ))
+    }
+
+    async fn refresh(&self, client: &Client, session: &Session) -> Result<Session, Error> {
+        let Credentials::Microsoft(credentials) = &session.credentials else {
+            return Err(Error::msg("Not a Microsoft account"));
+        };
+
+        let refresh_token_response =
+            refresh_token_response(&credentials.refresh_token, client).await?;
+        let xbox_response = xbox_response(&refresh_token_response.access_token, client).await?;
+        let xbox_security_token_response =
+            xbox_security_token_response(xbox_response.token, client).await?;
+        let minecraft_response = minecraft_response(
+            xbox_security_token_response.display_claims,
+            xbox_security_token_response.token,
+            client,
+        )
+        .await?;
+        let minecraft_profile_response =
+            minecraft_profile_response(&minecraft_response.access_token, client).await?;
+
+        Ok(Session::new(
+            session.account.active,
+            minecraft_profile_response.into(),
+            Credentials::Microsoft(MicrosoftCredentials {
+                access_token: minecraft_response.access_token,
+                refresh_token: refresh_token_response.refresh_token,
+                expiry: expiry_from_now(minecraft_response.expires_in.into())?,
+            }),
+        ))
+    }
+
+    fn needs_refresh(&self, session: &Session, margin: Duration) -> bool {
+        let Credentials::Microsoft(credentials) = &session.credentials else {
+            return false;
+        };
+
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .is_ok_and(|now| Duration::from_secs(credentials.expiry) <= now + margin)
+    }
+
+    // Microsoft offers no token revocation for device code logins.
+    async fn logout(&self, _client: &Client, _session: &Session) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+fn expiry_from_now(expires_in: u64) -> Result<u64, Error> {
+    let system_time = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    let combined_duration = system_time + Duration::from_secs(expires_in);
+    Ok(combined_duration.as_secs())
+}
+
+async fn device_response(client: &Client) -> Result<DeviceCodeResponse> {
+    let response = client
+        .get("https://login.microsoftonline.com/consumers/oauth2/v2.0/devicecode")
+        .form(&vec![
+            ("client_id", CLIENT_ID),
+            ("response_type", "code"),
+            ("scope", "XboxLive.signin offline_access"),
+        ])
+        .send()
+        .await?
+        .json::<DeviceCodeResponse>()
+        .await?;
+
+    Ok(response)
+}
+
+async fn authorization_token_response(
+    device_code: &str,
+    client: &Client,
+) -> Result<AuthorizationTokenResponse> {
+    let response = client
+        .post("https://login.microsoftonline.com/consumers/oauth2/v2.0/token")
+        .form(&vec![
+            ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+            ("client_id", CLIENT_ID),
+            ("device_code", device_code),
+        ])
+        .send()
+        .await?
+        .json::<AuthorizationTokenResponse>()
+        .await?;
+
+    Ok(response)
+}
+
+async fn refresh_token_response(
+    refresh_token: &str,
+    client: &Client,
+) -> Result<RefreshTokenResponse> {
+    let response = client
+        .post("https://login.microsoftonline.com/consumers/oauth2/v2.0/token")
+        .form(&vec![
+            ("grant_type", "refresh_token"),
+            ("client_id", CLIENT_ID),
+            ("refresh_token", refresh_token),
+        ])
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        return Err(match response.json::<OAuthErrorResponse>().await {
+            // Expired or revoked refresh tokens, e.g. after a password change.
+            Ok(error) if error.error == "invalid_grant" => {
+                CredentialsRejected(error.error_description).into()
+            }
+            Ok(error) => anyhow!("Token refresh failed: {}", error.error_description),
+            Err(_) => anyhow!("Token refresh failed: {}", status),
+        });
+    }
+
+    Ok(response.json::<RefreshTokenResponse>().await?)
+}
+
+async fn xbox_response(
+    access_token: &str,
+    client: &Client,
+) -> Result<XboxLiveAuthenticationResponse> {
+    let response = client
+        .post("https://user.auth.xboxlive.com/user/authenticate")
+        .json(&json!({
+                "Properties": {
+                "AuthMethod": "RPS",
+                "SiteName": "user.auth.xboxlive.com",
+                "RpsTicket": &format!("d={}", access_token)
+            },
+            "RelyingParty": "http://auth.xboxlive.com",
+            "TokenType": "JWT"
+        }))
+        .send()
+        .await?
+        .json::<XboxLiveAuthenticationResponse>()
+        .await?;
+
+    Ok(response)
+}
+
+async fn xbox_security_token_response(
+    token: String,
+    client: &Client,
+) -> Result<XboxLiveAuthenticationResponse> {
+    let response = client
+        .post("https://xsts.auth.xboxlive.com/xsts/authorize")
+        .json(&json!({
+            "Properties": {
+                "SandboxId": "RETAIL",
+                "UserTokens": [&token]
+            },
+            "RelyingParty": "rp://api.minecraftservices.com/",
+            "TokenType": "JWT"
+        }))
+        .send()
+        .await?
+        .json::<XboxLiveAuthenticationResponse>()
+        .await?;
+
+    Ok(response)
+}
+
+async fn minecraft_response(
+    display_claims: HashMap<String, Vec<HashMap<String, String>>>,
+    token: String,
+    client: &Client,
+) -> Result<MinecraftAuthenticationResponse, Error> {
+    let response = client
+        .post("https://api.minecraftservices.com/authentication/login_with_xbox")
+        .json(&json!({
+            "identityToken": &format!("XBL3.0 x={};{}", &display_claims["xui"][0]["uhs"], token)
+        }))
+        .send()
+        .await?
+        .json::<MinecraftAuthenticationResponse>()
+        .await?;
+
+    Ok(response)
+}
+
+async fn minecraft_profile_response(
+    access_token: &String,
+    client: &Client,
+) -> Result<MinecraftProfileResponse, Error> {
+    let response = client
+        .get("https://api.minecraftservices.com/minecraft/profile")
+        .bearer_auth(access_token)
+        .send()
+        .await?
+        .json::<MinecraftProfileResponse>()
+        .await?;
+
+    Ok(response)
+}
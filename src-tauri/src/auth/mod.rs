@@ -1,4 +1,9 @@
 pub mod account;
 pub mod auth;
 pub mod avatar;
+pub mod credential_store;
+pub mod microsoft;
+pub mod offline;
+pub mod provider;
 pub mod structs;
+pub mod yggdrasil;
@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use anyhow::{Error, Result, anyhow};
+use reqwest::Client;
+
+use super::{
+    account::{Credentials, Profile, Session},
+    provider::{AuthContext, AuthProvider},
+};
+
+pub struct OfflineProvider;
+
+impl AuthProvider for OfflineProvider {
+    type LoginRequest = String;
+
+    async fn login(&self, _context: &AuthContext<'_>, name: String) -> Result<Session, Error> {
+        let valid_name = (3..=16).contains(&name.len())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_name {
+            return Err(anyhow!(
+                "Offline names must be 3 to 16 characters of letters, digits or underscores"
+            ));
+        }
+
+        Ok(Session::new(
+            true,
+            Profile::offline(&name),
+            Credentials::Offline,
+        ))
+    }
+
+    // Offline accounts have no session to refresh.
+    async fn refresh(&self, _client: &Client, session: &Session) -> Result<Session, Error> {
+        Ok(session.clone())
+    }
+
+    fn needs_refresh(&self, _session: &Session, _margin: Duration) -> bool {
+        false
+    }
+
+    async fn logout(&self, _client: &Client, _session: &Session) -> Result<(), Error> {
+        Ok(())
+    }
+}
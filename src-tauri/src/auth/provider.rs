@@ -0,0 +1,94 @@
+use std::{fmt, time::Duration};
+
+use anyhow::{Error, Result};
+use reqwest::Client;
+use tauri::AppHandle;
+
+use super::{
+    account::{AccountKind, Session},
+    auth::LoginHandle,
+    microsoft::MicrosoftProvider,
+    offline::OfflineProvider,
+    yggdrasil::{YggdrasilLogin, YggdrasilProvider},
+};
+
+pub struct AuthContext<'a> {
+    pub client: &'a Client,
+    pub handle: &'a AppHandle,
+    pub login_handle: &'a LoginHandle,
+}
+
+// Returned by `refresh` when the server no longer accepts the stored credentials, the
+// user has to log in again.
+#[derive(Debug)]
+pub struct CredentialsRejected(pub String);
+
+impl fmt::Display for CredentialsRejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for CredentialsRejected {}
+
+pub trait AuthProvider {
+    type LoginRequest;
+
+    fn login(
+        &self,
+        context: &AuthContext<'_>,
+        request: Self::LoginRequest,
+    ) -> impl Future<Output = Result<Session, Error>> + Send;
+
+    fn refresh(
+        &self,
+        client: &Client,
+        session: &Session,
+    ) -> impl Future<Output = Result<Session, Error>> + Send;
+
+    fn needs_refresh(&self, session: &Session, margin: Duration) -> bool;
+
+    fn logout(
+        &self,
+        client: &Client,
+        session: &Session,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+}
+
+pub enum LoginRequest {
+    Microsoft,
+    Offline { name: String },
+    Yggdrasil(YggdrasilLogin),
+}
+
+pub async fn login(context: &AuthContext<'_>, request: LoginRequest) -> Result<Session, Error> {
+    match request {
+        LoginRequest::Microsoft => MicrosoftProvider.login(context, ()).await,
+        LoginRequest::Offline { name } => OfflineProvider.login(context, name).await,
+        LoginRequest::Yggdrasil(request) => YggdrasilProvider.login(context, request).await,
+    }
+}
+
+pub async fn refresh(client: &Client, session: &Session) -> Result<Session, Error> {
+    match session.account.kind {
+        AccountKind::Microsoft => MicrosoftProvider.refresh(client, session).await,
+        AccountKind::Offline => OfflineProvider.refresh(client, session).await,
+        AccountKind::Yggdrasil => YggdrasilProvider.refresh(client, session).await,
+    }
+}
+
+pub fn needs_refresh(session: &Session, margin: Duration) -> bool {
+    match session.account.kind {
+        AccountKind::Microsoft => MicrosoftProvider.needs_refresh(session, margin),
+        AccountKind::Offline => OfflineProvider.needs_refresh(session, margin),
+        AccountKind::Yggdrasil => YggdrasilProvider.needs_refresh(session, margin),
+    }
+}
+
+pub async fn logout(client: &Client, session: &Session) -> Result<(), Error> {
+    match session.account.kind {
+        AccountKind::Microsoft => MicrosoftProvider.logout(client, session).await,
+        AccountKind::Offline => OfflineProvider.logout(client, session).await,
+        AccountKind::Yggdrasil => YggdrasilProvider.logout(client, session).await,
+    }
+}
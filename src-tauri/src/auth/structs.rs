@@ -18,7 +18,7 @@ pub struct DeviceCodeResponse {
 pub struct AuthorizationTokenResponse {
     token_type: String,
     scope: String,
-    pub expires_in: u64,
+    expires_in: u64,
     ext_expires_in: u32,
     pub access_token: String,
     pub refresh_token: String,
@@ -29,7 +29,14 @@ pub struct RefreshTokenResponse {
     pub access_token: String,
     pub refresh_token: String,
     scope: String,
-    pub expires_in: u32,
+    expires_in: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OAuthErrorResponse {
+    pub error: String,
+    #[serde(default)]
+    pub error_description: String,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -46,7 +53,7 @@ pub struct MinecraftAuthenticationResponse {
     username: String,
     pub access_token: String,
     token_type: String,
-    expires_in: u32,
+    pub expires_in: u32,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -64,6 +71,7 @@ impl From<MinecraftProfileResponse> for Profile {
             name: val.name,
             skins: val.skins,
             capes: val.capes,
+            offline: false,
         }
     }
 }
@@ -0,0 +1,765 @@
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::{Context, Error, Result, anyhow};
+use base64::Engine;
+use log::{info, warn};
+use reqwest::{Client, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use tokio::fs;
+use uuid::Uuid;
+
+use super::{
+    account::{Credentials, Profile, Session, YggdrasilCredentials},
+    provider::{AuthContext, AuthProvider, CredentialsRejected},
+};
+use crate::config;
+
+const AUTHLIB_INJECTOR_LATEST_URL: &str = "https://authlib-injector.yushi.moe/artifact/latest.json";
+// Lets a server advertise its API root from a friendlier URL, see the authlib-injector
+// "API Location Indication" spec.
+const API_LOCATION_HEADER: &str = "X-Authlib-Injector-API-Location";
+
+pub struct YggdrasilLogin {
+    pub server: String,
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct AuthenticateResponse {
+    access_token: String,
+    client_token: String,
+    #[serde(default)]
+    available_profiles: Vec<GameProfile>,
+    selected_profile: Option<GameProfile>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct GameProfile {
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ErrorResponse {
+    error: Option<String>,
+    error_message: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ArtifactInfo {
+    version: String,
+    download_url: String,
+    checksums: ArtifactChecksums,
+}
+
+#[derive(Deserialize, Debug)]
+struct ArtifactChecksums {
+    sha256: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct InstalledAgent {
+    version: String,
+    sha256: String,
+}
+
+pub struct YggdrasilProvider;
+
+impl AuthProvider for YggdrasilProvider {
+    type LoginRequest = YggdrasilLogin;
+
+    async fn login(
+        &self,
+        context: &AuthContext<'_>,
+        request: YggdrasilLogin,
+    ) -> Result<Session, Error> {
+        authenticate(context.client, request).await
+    }
+
+    async fn refresh(&self, client: &Client, session: &Session) -> Result<Session, Error> {
+        let Credentials::Yggdrasil(credentials) = &session.credentials else {
+            return Err(anyhow!("Not a Yggdrasil account"));
+        };
+
+        if validate(client, credentials).await? {
+            return Ok(session.clone());
+        }
+
+        let response = refresh_token(
+            client,
+            &credentials.api_root,
+            &credentials.access_token,
+            &credentials.client_token,
+            None,
+        )
+        .await?;
+
+        into_session(
+            credentials.api_root.clone(),
+            response,
+            session.account.active,
+        )
+    }
+
+    // Yggdrasil tokens carry no expiry, they are checked against the server instead.
+    fn needs_refresh(&self, _session: &Session, _margin: Duration) -> bool {
+        true
+    }
+
+    async fn logout(&self, client: &Client, session: &Session) -> Result<(), Error> {
+        let Credentials::Yggdrasil(credentials) = &session.credentials else {
+            return Ok(());
+        };
+
+        post(
+            client,
+            &credentials.api_root,
+            "authserver/invalidate",
+            json!({
+                "accessToken": credentials.access_token,
+                "clientToken": credentials.client_token,
+            }),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+async fn authenticate(client: &Client, request: YggdrasilLogin) -> Result<Session, Error> {
+    let api_root = resolve_api_root(client, &request.server).await?;
+    // Fails early when the URL does not point at a Yggdrasil API.
+    fetch_metadata(client, &api_root).await?;
+
+    let response = post(
+        client,
+        &api_root,
+        "authserver/authenticate",
+        json!({
+            "agent": { "name": "Minecraft", "version": 1 },
+            "username": request.username,
+            "password": request.password,
+            "clientToken": Uuid::new_v4().simple().to_string(),
+            "requestUser": true,
+        }),
+    )
+    .await?
+    .json::<AuthenticateResponse>()
+    .await?;
+
+    // Servers hosting several profiles per user leave the choice to the client,
+    // binding the token to one profile is done through a refresh.
+    let response = match response.selected_profile {
+        Some(_) => response,
+        None => {
+            let profile = response
+                .available_profiles
+                .first()
+                .cloned()
+                .ok_or_else(|| anyhow!("No game profiles are available for this user"))?;
+            info!(
+                "{} has {} game profiles, binding the first one: {}",
+                request.username,
+                response.available_profiles.len(),
+                profile.name
+            );
+            refresh_token(
+                client,
+                &api_root,
+                &response.access_token,
+                &response.client_token,
+                Some(profile),
+            )
+            .await?
+        }
+    };
+
+    into_session(api_root, response, true)
+}
+
+fn into_session(
+    api_root: String,
+    response: AuthenticateResponse,
+    active: bool,
+) -> Result<Session, Error> {
+    let profile = response
+        .selected_profile
+        .ok_or_else(|| anyhow!("The server did not select a game profile"))?;
+
+    Ok(Session::new(
+        active,
+        Profile {
+            id: profile.id,
+            name: profile.name,
+            skins: Vec::new(),
+            capes: Vec::new(),
+            offline: false,
+        },
+        Credentials::Yggdrasil(YggdrasilCredentials {
+            api_root,
+            access_token: response.access_token,
+            client_token: response.client_token,
+        }),
+    ))
+}
+
+async fn resolve_api_root(client: &Client, server: &str) -> Result<String, Error> {
+    let server = server.trim();
+    let url = if server.contains("://") {
+        server.to_string()
+    } else {
+        format!("https://{}", server)
+    };
+    let mut url = Url::parse(&url).with_context(|| format!("Invalid server URL: {}", server))?;
+
+    let response = client.get(url.clone()).send().await?;
+    if let Some(location) = response
+        .headers()
+        .get(API_LOCATION_HEADER)
+        .and_then(|value| value.to_str().ok())
+    {
+        url = url.join(location)?;
+    }
+
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+
+    Ok(url.to_string())
+}
+
+async fn fetch_metadata(client: &Client, api_root: &str) -> Result<String, Error> {
+    let response = client.get(api_root).send().await?.error_for_status()?;
+    let metadata = response.text().await?;
+    serde_json::from_str::<Value>(&metadata)
+        .ok()
+        .filter(Value::is_object)
+        .ok_or_else(|| anyhow!("{} is not a Yggdrasil API root", api_root))?;
+
+    Ok(metadata)
+}
+
+async fn post(
+    client: &Client,
+    api_root: &str,
+    endpoint: &str,
+    body: Value,
+) -> Result<reqwest::Response, Error> {
+    let response = client
+        .post(format!("{}{}", api_root, endpoint))
+        .json(&body)
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        return Ok(response);
+    }
+
+    let status = response.status();
+    let message = match response.json::<ErrorResponse>().await {
+        Ok(error) => error
+            .error_message
+            .or(error.error)
+            .unwrap_or_else(|| status.to_string()),
+        Err(_) => status.to_string(),
+    };
+    let message = format!("{} failed: {}", endpoint, message);
+
+    // Yggdrasil answers invalid credentials and revoked tokens with 403 Forbidden.
+    if status == StatusCode::FORBIDDEN {
+        return Err(CredentialsRejected(message).into());
+    }
+    Err(anyhow!(message))
+}
+
+async fn refresh_token(
+    client: &Client,
+    api_root: &str,
+    access_token: &str,
+    client_token: &str,
+    selected_profile: Option<GameProfile>,
+) -> Result<AuthenticateResponse, Error> {
+    let mut body = json!({
+        "accessToken": access_token,
+        "clientToken": client_token,
+        "requestUser": true,
+    });
+    if let Some(profile) = selected_profile {
+        body["selectedProfile"] = serde_json::to_value(profile)?;
+    }
+
+    Ok(post(client, api_root, "authserver/refresh", body)
+        .await?
+        .json::<AuthenticateResponse>()
+        .await?)
+}
+
+async fn validate(client: &Client, credentials: &YggdrasilCredentials) -> Result<bool, Error> {
+    let response = client
+        .post(format!("{}authserver/validate", credentials.api_root))
+        .json(&json!({
+            "accessToken": credentials.access_token,
+            "clientToken": credentials.client_token,
+        }))
+        .send()
+        .await?;
+
+    Ok(response.status().is_success())
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+async fn ensure_authlib_injector(client: &Client) -> Result<PathBuf, Error> {
+    let agent_dir = config::get_config_dir()?.join("authlib-injector");
+    let jar_path = agent_dir.join("authlib-injector.jar");
+    let info_path = agent_dir.join("authlib-injector.json");
+
+    if jar_path.exists() && info_path.exists() {
+        let installed =
+            serde_json::from_str::<InstalledAgent>(&fs::read_to_string(&info_path).await?)?;
+        if sha256_hex(&fs::read(&jar_path).await?) == installed.sha256 {
+            return Ok(jar_path);
+        }
+        warn!("authlib-injector.jar does not match its checksum, downloading it again");
+    }
+
+    let artifact = client
+        .get(AUTHLIB_INJECTOR_LATEST_URL)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<ArtifactInfo>()
+        .await?;
+
+    info!("Downloading authlib-injector {}", artifact.version);
+    let data = client
+        .get(&artifact.download_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+
+    let sha256 = sha256_hex(&data);
+    if !sha256.eq_ignore_ascii_case(&artifact.checksums.sha256) {
+        return Err(anyhow!(
+            "authlib-injector {} failed checksum verification",
+            artifact.version
+        ));
+    }
+
+    fs::create_dir_all(&agent_dir).await?;
+    let temp_path = agent_dir.join("authlib-injector.jar.tmp");
+    fs::write(&temp_path, &data).await?;
+    fs::rename(&temp_path, &jar_path).await?;
+    fs::write(
+        &info_path,
+        serde_json::to_string_pretty(&InstalledAgent {
+            version: artifact.version,
+            sha256,
+        })?,
+    )
+    .await?;
+
+    Ok(jar_path)
+}
+
+pub async fn jvm_args(client: &Client, api_root: &str) -> Result<Vec<String>, Error> {
+    let jar_path = ensure_authlib_injector(client).await?;
+    let mut args = vec![format!("-javaagent:{}={}", jar_path.display(), api_root)];
+
+    // Saves authlib-injector a blocking request to the API root while the game starts.
+    match fetch_metadata(client, api_root).await {
+        Ok(metadata) => args.push(format!(
+            "-Dauthlibinjector.yggdrasil.prefetched={}",
+            base64::engine::general_purpose::STANDARD.encode(metadata)
+        )),
+        Err(e) => warn!("Failed to prefetch Yggdrasil metadata: {}", e),
+    }
+
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    struct Reply {
+        status: u16,
+        headers: Vec<(&'static str, &'static str)>,
+        body: Option<Value>,
+    }
+
+    impl Reply {
+        fn json(status: u16, body: Value) -> Self {
+            Reply {
+                status,
+                headers: Vec::new(),
+                body: Some(body),
+            }
+        }
+
+        fn empty(status: u16) -> Self {
+            Reply {
+                status,
+                headers: Vec::new(),
+                body: None,
+            }
+        }
+    }
+
+    type Requests = Arc<Mutex<Vec<(String, Value)>>>;
+
+    // A minimal HTTP/1.1 stand-in for a Yggdrasil server, answering one request per
+    // connection with whatever `route` returns for its path and JSON body.
+    async fn serve<F>(route: F) -> (String, Requests)
+    where
+        F: Fn(&str, &Value) -> Reply + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Requests::default();
+        let route = Arc::new(route);
+
+        let log = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let route = route.clone();
+                let log = log.clone();
+                tokio::spawn(async move {
+                    let mut stream = BufReader::new(stream);
+                    let mut line = String::new();
+                    stream.read_line(&mut line).await.unwrap();
+                    let path = line.split_whitespace().nth(1).unwrap().to_string();
+
+                    let mut length = 0;
+                    loop {
+                        let mut header = String::new();
+                        stream.read_line(&mut header).await.unwrap();
+                        let Some((name, value)) = header.trim_end().split_once(':') else {
+                            break;
+                        };
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().unwrap();
+                        }
+                    }
+                    let mut body = vec![0u8; length];
+                    stream.read_exact(&mut body).await.unwrap();
+                    let body = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+                    let reply = route(&path, &body);
+                    log.lock().unwrap().push((path, body));
+
+                    let content = reply.body.map(|body| body.to_string()).unwrap_or_default();
+                    let mut response = format!(
+                        "HTTP/1.1 {} Test\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+                        reply.status,
+                        content.len()
+                    );
+                    for (name, value) in reply.headers {
+                        response.push_str(&format!("{}: {}\r\n", name, value));
+                    }
+                    response.push_str("\r\n");
+                    response.push_str(&content);
+                    stream.write_all(response.as_bytes()).await.unwrap();
+                });
+            }
+        });
+
+        (base, requests)
+    }
+
+    fn paths(requests: &Requests) -> Vec<String> {
+        requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    fn body(requests: &Requests, path: &str) -> Value {
+        requests
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(p, _)| p == path)
+            .map(|(_, body)| body.clone())
+            .unwrap()
+    }
+
+    fn login(server: &str, password: &str) -> YggdrasilLogin {
+        YggdrasilLogin {
+            server: server.to_string(),
+            username: "alex@example.com".to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    fn session(api_root: &str) -> Session {
+        Session::new(
+            true,
+            Profile {
+                id: "abc".to_string(),
+                name: "Steve".to_string(),
+                skins: Vec::new(),
+                capes: Vec::new(),
+                offline: false,
+            },
+            Credentials::Yggdrasil(YggdrasilCredentials {
+                api_root: api_root.to_string(),
+                access_token: "old-access".to_string(),
+                client_token: "client".to_string(),
+            }),
+        )
+    }
+
+    fn credentials(session: &Session) -> &YggdrasilCredentials {
+        match &session.credentials {
+            Credentials::Yggdrasil(credentials) => credentials,
+            _ => panic!("not a Yggdrasil session"),
+        }
+    }
+
+    // Serves the API root under /api/yggdrasil/ and points to it from /.
+    fn api_root(path: &str) -> Option<Reply> {
+        match path {
+            "/" => Some(Reply {
+                status: 200,
+                headers: vec![(API_LOCATION_HEADER, "/api/yggdrasil")],
+                body: Some(json!({})),
+            }),
+            "/api/yggdrasil/" => Some(Reply::json(
+                200,
+                json!({ "meta": { "serverName": "Test" }, "skinDomains": [] }),
+            )),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn follows_api_location_header() {
+        let (base, _) = serve(|path, _| api_root(path).unwrap_or(Reply::empty(404))).await;
+        let client = Client::new();
+
+        let root = resolve_api_root(&client, &base).await.unwrap();
+        assert_eq!(root, format!("{}/api/yggdrasil/", base));
+
+        let root = resolve_api_root(&client, &format!("{}/api/yggdrasil", base))
+            .await
+            .unwrap();
+        assert_eq!(root, format!("{}/api/yggdrasil/", base));
+    }
+
+    #[tokio::test]
+    async fn authenticate_with_selected_profile() {
+        let (base, requests) = serve(|path, body| {
+            api_root(path).unwrap_or_else(|| match path {
+                "/api/yggdrasil/authserver/authenticate" => Reply::json(
+                    200,
+                    json!({
+                        "accessToken": "access",
+                        "clientToken": body["clientToken"],
+                        "availableProfiles": [{ "id": "abc", "name": "Steve" }],
+                        "selectedProfile": { "id": "abc", "name": "Steve" },
+                    }),
+                ),
+                _ => Reply::empty(404),
+            })
+        })
+        .await;
+
+        let session = authenticate(&Client::new(), login(&base, "secret"))
+            .await
+            .unwrap();
+
+        assert_eq!(session.account.profile.name, "Steve");
+        let credentials = credentials(&session);
+        assert_eq!(credentials.api_root, format!("{}/api/yggdrasil/", base));
+        assert_eq!(credentials.access_token, "access");
+
+        let request = body(&requests, "/api/yggdrasil/authserver/authenticate");
+        assert_eq!(request["username"], "alex@example.com");
+        assert_eq!(request["password"], "secret");
+        assert_eq!(credentials.client_token, request["clientToken"]);
+        assert!(!paths(&requests).iter().any(|p| p.ends_with("/refresh")));
+    }
+
+    #[tokio::test]
+    async fn authenticate_binds_first_available_profile() {
+        let (base, requests) = serve(|path, body| {
+            api_root(path).unwrap_or_else(|| match path {
+                "/api/yggdrasil/authserver/authenticate" => Reply::json(
+                    200,
+                    json!({
+                        "accessToken": "unbound",
+                        "clientToken": body["clientToken"],
+                        "availableProfiles": [
+                            { "id": "def", "name": "Alex" },
+                            { "id": "abc", "name": "Steve" },
+                        ],
+                    }),
+                ),
+                "/api/yggdrasil/authserver/refresh" => Reply::json(
+                    200,
+                    json!({
+                        "accessToken": "bound",
+                        "clientToken": body["clientToken"],
+                        "selectedProfile": body["selectedProfile"],
+                    }),
+                ),
+                _ => Reply::empty(404),
+            })
+        })
+        .await;
+
+        let session = authenticate(&Client::new(), login(&base, "secret"))
+            .await
+            .unwrap();
+
+        assert_eq!(session.account.profile.id, "def");
+        assert_eq!(session.account.profile.name, "Alex");
+        assert_eq!(credentials(&session).access_token, "bound");
+
+        let request = body(&requests, "/api/yggdrasil/authserver/refresh");
+        assert_eq!(request["accessToken"], "unbound");
+        assert_eq!(
+            request["selectedProfile"],
+            json!({ "id": "def", "name": "Alex" })
+        );
+    }
+
+    #[tokio::test]
+    async fn rejected_password_is_credentials_rejected() {
+        let (base, _) = serve(|path, _| {
+            api_root(path).unwrap_or_else(|| {
+                Reply::json(
+                    403,
+                    json!({
+                        "error": "ForbiddenOperationException",
+                        "errorMessage": "Invalid credentials. Invalid username or password.",
+                    }),
+                )
+            })
+        })
+        .await;
+
+        let error = authenticate(&Client::new(), login(&base, "wrong"))
+            .await
+            .unwrap_err();
+
+        let rejected = error.downcast_ref::<CredentialsRejected>().unwrap();
+        assert_eq!(
+            rejected.0,
+            "authserver/authenticate failed: Invalid credentials. Invalid username or password."
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_keeps_valid_token() {
+        let (base, requests) = serve(|path, _| match path {
+            "/authserver/validate" => Reply::empty(204),
+            _ => Reply::empty(404),
+        })
+        .await;
+        let session = session(&format!("{}/", base));
+
+        let refreshed = YggdrasilProvider
+            .refresh(&Client::new(), &session)
+            .await
+            .unwrap();
+
+        assert_eq!(credentials(&refreshed).access_token, "old-access");
+        assert_eq!(paths(&requests), ["/authserver/validate"]);
+    }
+
+    #[tokio::test]
+    async fn refresh_renews_invalid_token() {
+        let (base, requests) = serve(|path, body| match path {
+            "/authserver/validate" => Reply::json(
+                403,
+                json!({ "error": "ForbiddenOperationException", "errorMessage": "Invalid token." }),
+            ),
+            "/authserver/refresh" => Reply::json(
+                200,
+                json!({
+                    "accessToken": "new-access",
+                    "clientToken": body["clientToken"],
+                    "selectedProfile": { "id": "abc", "name": "Steve" },
+                }),
+            ),
+            _ => Reply::empty(404),
+        })
+        .await;
+        let session = session(&format!("{}/", base));
+
+        let refreshed = YggdrasilProvider
+            .refresh(&Client::new(), &session)
+            .await
+            .unwrap();
+
+        assert_eq!(credentials(&refreshed).access_token, "new-access");
+        assert_eq!(credentials(&refreshed).client_token, "client");
+        assert!(refreshed.account.active);
+
+        let request = body(&requests, "/authserver/refresh");
+        assert_eq!(request["accessToken"], "old-access");
+        assert!(request.get("selectedProfile").is_none());
+    }
+
+    #[tokio::test]
+    async fn refresh_with_revoked_token_is_credentials_rejected() {
+        let (base, _) = serve(|_, _| {
+            Reply::json(
+                403,
+                json!({ "error": "ForbiddenOperationException", "errorMessage": "Invalid token." }),
+            )
+        })
+        .await;
+        let session = session(&format!("{}/", base));
+
+        let error = YggdrasilProvider
+            .refresh(&Client::new(), &session)
+            .await
+            .unwrap_err();
+
+        assert!(error.downcast_ref::<CredentialsRejected>().is_some());
+    }
+
+    #[tokio::test]
+    async fn logout_invalidates_token() {
+        let (base, requests) = serve(|path, _| match path {
+            "/authserver/invalidate" => Reply::empty(204),
+            _ => Reply::empty(404),
+        })
+        .await;
+        let session = session(&format!("{}/", base));
+
+        YggdrasilProvider
+            .logout(&Client::new(), &session)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            body(&requests, "/authserver/invalidate"),
+            json!({ "accessToken": "old-access", "clientToken": "client" })
+        );
+    }
+}
@@ -6,23 +6,40 @@ use tauri::{AppHandle, Manager, State};
 
 use crate::{
     AppState,
-    auth::{
-        self,
-        account::{Account, Profile},
-    },
+    auth::{self, account::AccountSummary, provider::LoginRequest, yggdrasil::YggdrasilLogin},
     config::{self, LauncherSettings},
     discord,
     instance::Instance,
-    java::{self, detect::JavaDetectionResult, structs::JavaConfig, test::JavaTestInfo},
-    resources::{self, screenshots::Screenshot, versions::Version, worlds::World},
+    java::{
+        self, detect::JavaDetectionResult, memory::MemorySettings, structs::JavaConfig,
+        test::JavaTestInfo,
+    },
+    resources::{
+        self,
+        backups::{RestoreTarget, WorldBackup},
+        launch_state::{LaunchError, LaunchState},
+        quick_play::{QuickPlayEntry, QuickPlayTarget},
+        screenshots::{ScreenshotPage, TrashedScreenshot},
+        server_ping::ServerStatus,
+        servers::Server,
+        stats::InstanceStats,
+        versions::Version,
+        worlds::{
+            World,
+            players::{InventoryItem, PlayerSummary},
+        },
+    },
 };
 
 #[tauri::command]
 #[specta::specta]
-pub async fn login(state: State<'_, AppState>, handle: AppHandle) -> Result<Profile, String> {
+pub async fn login(
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> Result<AccountSummary, String> {
     let login_handle = state.login_handle.clone();
-    match auth::auth::login(&state, handle, login_handle).await {
-        Ok(profile_response) => Ok(Profile::from(profile_response)),
+    match auth::auth::login(&state, handle, login_handle, LoginRequest::Microsoft).await {
+        Ok(account) => Ok(account),
         Err(e) => {
             error!("Login failed: {}", e);
             Err(e.to_string())
@@ -30,6 +47,48 @@ pub async fn login(state: State<'_, AppState>, handle: AppHandle) -> Result<Prof
     }
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn add_offline_account(
+    state: State<'_, AppState>,
+    handle: AppHandle,
+    name: String,
+) -> Result<AccountSummary, String> {
+    let login_handle = state.login_handle.clone();
+    let request = LoginRequest::Offline { name: name.clone() };
+    match auth::auth::login(&state, handle, login_handle, request).await {
+        Ok(account) => Ok(account),
+        Err(e) => {
+            error!("Failed to add offline account {}: {}", name, e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn login_yggdrasil(
+    state: State<'_, AppState>,
+    handle: AppHandle,
+    server: String,
+    username: String,
+    password: String,
+) -> Result<AccountSummary, String> {
+    let login_handle = state.login_handle.clone();
+    let request = LoginRequest::Yggdrasil(YggdrasilLogin {
+        server: server.clone(),
+        username,
+        password,
+    });
+    match auth::auth::login(&state, handle, login_handle, request).await {
+        Ok(account) => Ok(account),
+        Err(e) => {
+            error!("Yggdrasil login to {} failed: {}", server, e);
+            Err(e.to_string())
+        }
+    }
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn cancel_login(state: State<'_, AppState>) -> Result<(), String> {
@@ -46,37 +105,37 @@ pub fn set_onboarding_complete() -> Result<(), String> {
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_minecraft_profiles() -> Result<Vec<Profile>, String> {
-    let config = config::get_config().map_err(|e| e.to_string())?;
-    let default_account = Account::default();
-    let accounts = config.accounts;
-    let mut profiles = Vec::new();
-    for account in accounts {
-        if account != default_account {
-            profiles.push(account.profile);
-        }
-    }
-    Ok(profiles)
+pub async fn get_minecraft_profiles() -> Result<Vec<AccountSummary>, String> {
+    auth::auth::get_account_summaries()
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn switch_account(id: String) -> Result<(), String> {
-    auth::auth::switch_account(id).map_err(|e| e.to_string())?;
+pub async fn switch_account(id: String) -> Result<(), String> {
+    auth::auth::switch_account(id)
+        .await
+        .map_err(|e| e.to_string())?;
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn delete_account(id: String) -> Result<(), String> {
-    auth::auth::delete_account(id).map_err(|e| e.to_string())?;
+pub async fn delete_account(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    let client = state.client.lock().await;
+    auth::auth::delete_account(&client, id)
+        .await
+        .map_err(|e| e.to_string())?;
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_active_account() -> Result<Option<Account>, String> {
-    let account = auth::auth::get_active_account().map_err(|e| e.to_string())?;
+pub async fn get_active_account() -> Result<Option<AccountSummary>, String> {
+    let account = auth::auth::get_active_account_summary()
+        .await
+        .map_err(|e| e.to_string())?;
     Ok(account)
 }
 
@@ -131,6 +190,12 @@ pub fn save_java_to_config(
     java::config::save_java_to_config(paths, automatic).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn get_system_memory() -> Result<Option<u64>, String> {
+    Ok(java::memory::total_system_memory())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_java_from_config() -> Result<JavaConfig, String> {
@@ -161,6 +226,8 @@ pub async fn create_instance(
     handle: AppHandle,
     instance: Instance,
 ) -> Result<(), String> {
+    validate_memory(instance.java.memory.as_ref())?;
+
     let mut instances_lock = state.instances.lock().await;
     instances_lock
         .add_instance(&state, &handle, instance)
@@ -176,6 +243,8 @@ pub async fn update_instance(
     handle: AppHandle,
     instance: Instance,
 ) -> Result<(), String> {
+    validate_memory(instance.java.memory.as_ref())?;
+
     let mut instances_lock = state.instances.lock().await;
     instances_lock
         .update_instance(&handle, instance)
@@ -183,6 +252,18 @@ pub async fn update_instance(
     Ok(())
 }
 
+// Memory limits are checked when the user edits them, not on every internal write, so
+// a later change in system memory cannot block recording play time.
+fn validate_memory(memory: Option<&MemorySettings>) -> Result<(), String> {
+    match memory.map(MemorySettings::validate) {
+        Some(Err(e)) => {
+            error!("Invalid memory settings: {}", e);
+            Err(e.to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn delete_instance(
@@ -203,11 +284,30 @@ pub async fn launch_instance(
     state: State<'_, AppState>,
     handle: AppHandle,
     slug: String,
-) -> Result<(), String> {
-    match resources::launch::launch(state, handle, &slug).await {
+    target: Option<QuickPlayTarget>,
+    allow_downgrade: Option<bool>,
+) -> Result<(), LaunchError> {
+    let allow_downgrade = allow_downgrade.unwrap_or(false);
+    match resources::launch::launch(state, handle, &slug, target, allow_downgrade).await {
         Ok(_) => Ok(()),
         Err(e) => {
             error!("Error launching instance: {:?}", e);
+            Err(LaunchError::from(e))
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_instance(
+    state: State<'_, AppState>,
+    handle: AppHandle,
+    slug: String,
+) -> Result<(), String> {
+    match resources::launch::stop_instance(state, handle, &slug).await {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error!("Failed to stop instance {}: {}", slug, e);
             Err(e.to_string())
         }
     }
@@ -229,6 +329,38 @@ pub async fn kill_instance(
     }
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn get_instance_state(
+    state: State<'_, AppState>,
+    slug: String,
+) -> Result<Option<LaunchState>, String> {
+    Ok(resources::launch_state::get_instance_state(state, &slug))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_instance_stats(
+    state: State<'_, AppState>,
+    slug: String,
+) -> Result<Vec<InstanceStats>, String> {
+    resources::stats::get_instance_stats(state, &slug)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_quick_play_history(slug: String) -> Result<Vec<QuickPlayEntry>, String> {
+    match resources::quick_play::get_history(&slug) {
+        Ok(history) => Ok(history),
+        Err(e) => {
+            error!("Failed to get quick play history for {}: {}", slug, e);
+            Err(e.to_string())
+        }
+    }
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn get_versions(state: State<'_, AppState>) -> Result<Vec<Version>, String> {
@@ -267,6 +399,9 @@ pub fn get_launcher_settings() -> Result<LauncherSettings, String> {
     Ok(LauncherSettings {
         rich_presence: config.rich_presence,
         use_discrete_gpu: config.use_discrete_gpu,
+        stop_grace_period: config.stop_grace_period,
+        default_memory: config.default_memory,
+        launch_environment: config.launch_environment,
     })
 }
 
@@ -276,6 +411,7 @@ pub async fn save_launcher_settings(
     handle: AppHandle,
     settings: LauncherSettings,
 ) -> Result<(), String> {
+    validate_memory(Some(&settings.default_memory))?;
     config::update_launcher_settings(&handle, &settings).map_err(|e| e.to_string())?;
     let discord_client = &handle.state::<AppState>().discord_client;
     discord::toggle_rpc(discord_client, settings.rich_presence)
@@ -299,8 +435,12 @@ pub async fn get_avatar(state: State<'_, AppState>, uuid: String) -> Result<Stri
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_screenshots(slug: String) -> Result<Vec<Screenshot>, String> {
-    match resources::screenshots::get_screenshots(slug) {
+pub async fn get_screenshots(
+    slug: String,
+    offset: usize,
+    limit: usize,
+) -> Result<ScreenshotPage, String> {
+    match resources::screenshots::get_screenshots(slug, offset, limit).await {
         Ok(screenshots) => Ok(screenshots),
         Err(e) => {
             error!("Failed to get screenshots: {}", e);
@@ -309,6 +449,137 @@ pub fn get_screenshots(slug: String) -> Result<Vec<Screenshot>, String> {
     }
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn get_screenshot_gallery(
+    state: State<'_, AppState>,
+    offset: usize,
+    limit: usize,
+) -> Result<ScreenshotPage, String> {
+    match resources::screenshots::get_screenshot_gallery(&state, offset, limit).await {
+        Ok(screenshots) => Ok(screenshots),
+        Err(e) => {
+            error!("Failed to get screenshot gallery: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn delete_screenshots(
+    slug: String,
+    names: Vec<String>,
+) -> Result<Vec<TrashedScreenshot>, String> {
+    match resources::screenshots::delete_screenshots(&slug, &names) {
+        Ok(trashed) => Ok(trashed),
+        Err(e) => {
+            error!("Failed to delete screenshots of {}: {}", slug, e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_trashed_screenshots() -> Result<Vec<TrashedScreenshot>, String> {
+    match resources::screenshots::get_trashed_screenshots() {
+        Ok(trashed) => Ok(trashed),
+        Err(e) => {
+            error!("Failed to get trashed screenshots: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn restore_screenshot(id: String) -> Result<String, String> {
+    match resources::screenshots::restore_screenshot(&id) {
+        Ok(name) => Ok(name),
+        Err(e) => {
+            error!("Failed to restore screenshot {}: {}", id, e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn empty_screenshot_trash() -> Result<(), String> {
+    match resources::screenshots::empty_screenshot_trash() {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error!("Failed to empty screenshot trash: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn rename_screenshot(slug: String, name: String, new_name: String) -> Result<String, String> {
+    match resources::screenshots::rename_screenshot(&slug, &name, &new_name) {
+        Ok(new_name) => Ok(new_name),
+        Err(e) => {
+            error!("Failed to rename screenshot {} of {}: {}", name, slug, e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn copy_screenshot_to_clipboard(
+    handle: AppHandle,
+    slug: String,
+    name: String,
+) -> Result<(), String> {
+    match resources::screenshots::copy_screenshot_to_clipboard(&handle, &slug, &name).await {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error!("Failed to copy screenshot {} to clipboard: {}", name, e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn export_screenshots(
+    slug: String,
+    names: Vec<String>,
+    destination: PathBuf,
+) -> Result<PathBuf, String> {
+    match resources::screenshots::export_screenshots(&slug, &names, destination).await {
+        Ok(archive_path) => Ok(archive_path),
+        Err(e) => {
+            error!("Failed to export screenshots of {}: {}", slug, e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn move_screenshots(
+    state: State<'_, AppState>,
+    from_slug: String,
+    to_slug: String,
+    names: Vec<String>,
+) -> Result<Vec<String>, String> {
+    match resources::screenshots::move_screenshots(&state, &from_slug, &to_slug, &names).await {
+        Ok(moved) => Ok(moved),
+        Err(e) => {
+            error!(
+                "Failed to move screenshots from {} to {}: {}",
+                from_slug, to_slug, e
+            );
+            Err(e.to_string())
+        }
+    }
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn open_screenshots_dir(slug: String) -> Result<(), String> {
@@ -338,15 +609,34 @@ pub async fn watch_screenshots_for_instance(handle: AppHandle, slug: String) ->
 
 #[tauri::command]
 #[specta::specta]
-pub fn stop_watching_screenshots() -> Result<(), String> {
-    resources::screenshots::stop_watching_screenshots();
+pub fn stop_watching_screenshots(slug: Option<String>) -> Result<(), String> {
+    resources::screenshots::stop_watching_screenshots(slug.as_deref());
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_worlds(slug: String) -> Result<Vec<World>, String> {
-    match resources::worlds::get_worlds(slug) {
+pub async fn watch_instance_files(handle: AppHandle, slug: String) -> Result<(), String> {
+    match resources::watcher::watch_instance(handle, &slug) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error!("Failed to start file watcher for {}: {}", slug, e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn unwatch_instance_files(slug: String) -> Result<(), String> {
+    resources::watcher::unwatch_instance(&slug);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_worlds(state: State<'_, AppState>, slug: String) -> Result<Vec<World>, String> {
+    match resources::worlds::get_worlds(state, slug).await {
         Ok(worlds) => Ok(worlds),
         Err(e) => {
             error!("Failed to get worlds: {}", e);
@@ -394,3 +684,284 @@ pub async fn delete_world(
         }
     }
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_world_players(
+    slug: String,
+    world_name: String,
+) -> Result<Vec<PlayerSummary>, String> {
+    match resources::worlds::players::get_world_players(slug, world_name).await {
+        Ok(players) => Ok(players),
+        Err(e) => {
+            error!("Failed to get world players: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_player_inventory(
+    slug: String,
+    world_name: String,
+    uuid: String,
+) -> Result<Vec<InventoryItem>, String> {
+    match resources::worlds::players::get_player_inventory(slug, world_name, uuid).await {
+        Ok(inventory) => Ok(inventory),
+        Err(e) => {
+            error!("Failed to get player inventory: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn import_world(
+    state: State<'_, AppState>,
+    slug: String,
+    source: PathBuf,
+) -> Result<String, String> {
+    match resources::worlds::import_world(&state, &slug, source).await {
+        Ok(folder_name) => Ok(folder_name),
+        Err(e) => {
+            error!("Failed to import world: {:?}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn export_world(
+    state: State<'_, AppState>,
+    slug: String,
+    world_name: String,
+    destination: PathBuf,
+) -> Result<PathBuf, String> {
+    match resources::worlds::export_world(&state, &slug, &world_name, destination).await {
+        Ok(archive_path) => Ok(archive_path),
+        Err(e) => {
+            error!("Failed to export world: {:?}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn duplicate_world(
+    state: State<'_, AppState>,
+    slug: String,
+    world_name: String,
+    new_folder_name: String,
+) -> Result<(), String> {
+    match resources::worlds::duplicate_world(&state, &slug, &world_name, &new_folder_name).await {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error!("Failed to duplicate world: {:?}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn rename_world(
+    state: State<'_, AppState>,
+    slug: String,
+    world_name: String,
+    new_name: String,
+) -> Result<(), String> {
+    match resources::worlds::rename_world(&state, &slug, &world_name, &new_name).await {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error!("Failed to rename world: {:?}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn copy_world_to_instance(
+    state: State<'_, AppState>,
+    from_slug: String,
+    world_name: String,
+    to_slug: String,
+) -> Result<String, String> {
+    match resources::worlds::copy_world_to_instance(&state, &from_slug, &world_name, &to_slug).await
+    {
+        Ok(folder_name) => Ok(folder_name),
+        Err(e) => {
+            error!("Failed to copy world: {:?}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn backup_world(
+    state: State<'_, AppState>,
+    slug: String,
+    world_name: String,
+) -> Result<WorldBackup, String> {
+    match resources::backups::backup_world(&state, &slug, &world_name).await {
+        Ok(backup) => Ok(backup),
+        Err(e) => {
+            error!("Failed to back up world: {:?}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_world_backups(
+    slug: String,
+    world_name: Option<String>,
+) -> Result<Vec<WorldBackup>, String> {
+    match resources::backups::get_backups(&slug, world_name.as_deref()).await {
+        Ok(backups) => Ok(backups),
+        Err(e) => {
+            error!("Failed to get world backups: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_world_backup(
+    state: State<'_, AppState>,
+    slug: String,
+    world_name: String,
+    backup_id: String,
+    target: RestoreTarget,
+) -> Result<String, String> {
+    match resources::backups::restore_backup(&state, &slug, &world_name, &backup_id, target).await {
+        Ok(folder_name) => Ok(folder_name),
+        Err(e) => {
+            error!("Failed to restore world backup: {:?}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_world_backup(
+    slug: String,
+    world_name: String,
+    backup_id: String,
+) -> Result<(), String> {
+    match resources::backups::delete_backup(&slug, &world_name, &backup_id).await {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error!("Failed to delete world backup: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn prune_world_backups(
+    state: State<'_, AppState>,
+    slug: String,
+    world_name: String,
+) -> Result<usize, String> {
+    match resources::backups::prune_backups(&state, &slug, &world_name).await {
+        Ok(removed) => Ok(removed),
+        Err(e) => {
+            error!("Failed to prune world backups: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_servers(slug: String) -> Result<Vec<Server>, String> {
+    match resources::servers::get_servers(&slug) {
+        Ok(servers) => Ok(servers),
+        Err(e) => {
+            error!("Failed to get servers: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn add_server(slug: String, server: Server) -> Result<(), String> {
+    match resources::servers::add_server(&slug, server) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error!("Failed to add server: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_server(slug: String, index: usize, server: Server) -> Result<(), String> {
+    match resources::servers::update_server(&slug, index, server) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error!("Failed to update server: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn remove_server(slug: String, index: usize) -> Result<(), String> {
+    match resources::servers::remove_server(&slug, index) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error!("Failed to remove server: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn move_server(slug: String, from: usize, to: usize) -> Result<(), String> {
+    match resources::servers::move_server(&slug, from, to) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error!("Failed to move server: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn copy_servers(from_slug: String, to_slug: String) -> Result<usize, String> {
+    match resources::servers::copy_servers(&from_slug, &to_slug) {
+        Ok(copied) => Ok(copied),
+        Err(e) => {
+            error!("Failed to copy servers: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn ping_server(address: String) -> Result<ServerStatus, String> {
+    match resources::server_ping::ping_server(&address).await {
+        Ok(status) => Ok(status),
+        Err(e) => {
+            error!("Failed to ping server {}: {}", address, e);
+            Err(e.to_string())
+        }
+    }
+}
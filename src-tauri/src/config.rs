@@ -6,17 +6,31 @@ use serde::{Deserialize, Serialize};
 use specta::Type;
 use tauri::AppHandle;
 
-use crate::{auth::account::Account, java::structs::JavaConfig};
+use crate::{
+    auth::{
+        self,
+        account::{self, Account},
+    },
+    java::{memory::MemorySettings, structs::JavaConfig},
+    resources::environment::LaunchEnvironment,
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Config {
+    #[serde(deserialize_with = "account::deserialize_accounts")]
     pub accounts: Vec<Account>,
     pub rich_presence: bool,
     pub java: JavaConfig,
     pub completed_onboarding: bool,
     #[serde(default)]
     pub use_discrete_gpu: bool,
+    #[serde(default = "default_stop_grace_period")]
+    pub stop_grace_period: u64,
+    #[serde(default)]
+    pub default_memory: MemorySettings,
+    #[serde(default)]
+    pub launch_environment: LaunchEnvironment,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
@@ -24,6 +38,13 @@ pub struct Config {
 pub struct LauncherSettings {
     pub rich_presence: bool,
     pub use_discrete_gpu: bool,
+    pub stop_grace_period: u64,
+    pub default_memory: MemorySettings,
+    pub launch_environment: LaunchEnvironment,
+}
+
+fn default_stop_grace_period() -> u64 {
+    10
 }
 
 fn create_config_file(config: &Config) -> Result<(), Error> {
@@ -34,8 +55,11 @@ fn create_config_file(config: &Config) -> Result<(), Error> {
             .map_err(|e| anyhow!("Failed to create config directory: {}", e))?;
     }
 
-    let config_data = serde_json::to_string_pretty(config)?;
-    fs::write(config_path, config_data)
+    write_config_file(&serde_json::to_string_pretty(config)?)
+}
+
+fn write_config_file(config_data: &str) -> Result<(), Error> {
+    fs::write(get_config_path()?, config_data)
         .map_err(|e| anyhow!("Failed to write config file: {}", e))?;
     Ok(())
 }
@@ -63,14 +87,15 @@ pub fn config_file_exists() -> Result<bool, Error> {
 }
 
 pub fn create_default_config_file() -> Result<(), Error> {
-    let default_account = Account::default();
-
     let default_config = Config {
-        accounts: vec![default_account],
+        accounts: Vec::new(),
         rich_presence: true,
         java: JavaConfig::default(),
         completed_onboarding: false,
         use_discrete_gpu: true,
+        stop_grace_period: default_stop_grace_period(),
+        default_memory: MemorySettings::default(),
+        launch_environment: LaunchEnvironment::default(),
     };
 
     create_config_file(&default_config)
@@ -86,9 +111,29 @@ pub fn get_config() -> Result<Config, Error> {
 }
 
 pub fn save_config(config: &Config) -> Result<(), Error> {
+    // Older configs still hold the account tokens until they are moved to the
+    // credential store, dropping them before that would sign every account out.
+    if !auth::auth::credentials_migrated() {
+        if let Some(accounts) = get_raw_accounts()? {
+            let mut raw = serde_json::to_value(config)?;
+            raw["accounts"] = accounts;
+            return write_config_file(&serde_json::to_string_pretty(&raw)?);
+        }
+    }
+
     create_config_file(config)
 }
 
+// The accounts as written in the config file, before older formats are converted.
+pub fn get_raw_accounts() -> Result<Option<serde_json::Value>, Error> {
+    let config_data = fs::read_to_string(get_config_path()?)
+        .map_err(|e| anyhow!("Failed to read config file: {}", e))?;
+    let mut raw = serde_json::from_str::<serde_json::Value>(&config_data)
+        .map_err(|e| anyhow!("Failed to parse config file: {}", e))?;
+
+    Ok(raw.get_mut("accounts").map(serde_json::Value::take))
+}
+
 pub fn set_onboarding_complete() -> Result<(), Error> {
     let mut config = get_config()?;
     config.completed_onboarding = true;
@@ -103,6 +148,9 @@ pub fn update_launcher_settings(
     let mut config = get_config()?;
     config.rich_presence = new_settings.rich_presence;
     config.use_discrete_gpu = new_settings.use_discrete_gpu;
+    config.stop_grace_period = new_settings.stop_grace_period;
+    config.default_memory = new_settings.default_memory;
+    config.launch_environment = new_settings.launch_environment.clone();
     save_config(&config)?; // Maybe need to emit an event here for the frontend
 
     Ok(())
@@ -7,7 +7,14 @@ use specta::Type;
 use tauri::{AppHandle, State};
 use tauri_specta::Event;
 
-use crate::{AppState, config, resources::version};
+use crate::{
+    AppState, config,
+    java::memory::{JvmPreset, MemorySettings},
+    resources::{
+        backups::BackupSettings, environment::LaunchEnvironment, hooks::LaunchHooks, screenshots,
+        version, watcher,
+    },
+};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct InstanceConfig {
@@ -41,6 +48,10 @@ pub struct Java {
     pub path: String,
     pub args: Vec<String>,
     pub version: u8,
+    #[serde(default)]
+    pub memory: Option<MemorySettings>,
+    #[serde(default)]
+    pub preset: JvmPreset,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
@@ -56,6 +67,12 @@ pub struct Settings {
     #[serde(default)]
     #[specta(type = String)]
     pub last_played: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub environment: LaunchEnvironment,
+    #[serde(default)]
+    pub hooks: LaunchHooks,
+    #[serde(default)]
+    pub backups: BackupSettings,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Type, Event)]
@@ -124,30 +141,13 @@ impl InstanceConfig {
         let config = config::get_config()?;
         let java_config = config.java;
 
-        match java_version {
-            8 => {
-                instance.java = Java {
-                    path: java_config.java_8_path,
-                    args: vec![],
-                    version: 8,
-                };
-            }
-            17 => {
-                instance.java = Java {
-                    path: java_config.java_17_path,
-                    args: vec![],
-                    version: 17,
-                };
-            }
-            21 => {
-                instance.java = Java {
-                    path: java_config.java_21_path,
-                    args: vec![],
-                    version: 21,
-                };
-            }
+        instance.java.path = match java_version {
+            8 => java_config.java_8_path,
+            17 => java_config.java_17_path,
+            21 => java_config.java_21_path,
             _ => return Err(anyhow!("Unsupported Java version: {}", java_version)),
-        }
+        };
+        instance.java.version = java_version as u8;
 
         self.instances.push(instance);
         self.write_to_file()?;
@@ -172,6 +172,10 @@ impl InstanceConfig {
         self.instances.retain(|i| i.slug != slug);
         self.write_to_file()?;
 
+        // Watched directories cannot be removed on Windows.
+        screenshots::stop_watching_screenshots(Some(slug));
+        watcher::stop_watching_instance(slug);
+
         let instances_path = config::get_config_dir()?.join("instances");
         let instance_dir = instances_path.join(slug);
         if instance_dir.exists() {
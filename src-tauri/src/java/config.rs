@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use anyhow::{Error, Result};
 use log::info;
 
-use crate::config::{self, Config};
+use crate::config;
 
 use super::structs::JavaConfig;
 
@@ -43,20 +43,14 @@ pub fn save_java_to_config(
     info!("Java 17 path: {:?}", processed_paths.1);
     info!("Java 21 path: {:?}", processed_paths.2);
 
-    let config = config::get_config()?;
-    let new_config = Config {
-        accounts: config.accounts,
-        rich_presence: config.rich_presence,
-        java: JavaConfig {
-            java_8_path: processed_paths.0.to_string_lossy().to_string(),
-            java_17_path: processed_paths.1.to_string_lossy().to_string(),
-            java_21_path: processed_paths.2.to_string_lossy().to_string(),
-        },
-        completed_onboarding: config.completed_onboarding,
-        use_discrete_gpu: config.use_discrete_gpu,
+    let mut config = config::get_config()?;
+    config.java = JavaConfig {
+        java_8_path: processed_paths.0.to_string_lossy().to_string(),
+        java_17_path: processed_paths.1.to_string_lossy().to_string(),
+        java_21_path: processed_paths.2.to_string_lossy().to_string(),
     };
 
-    config::save_config(&new_config)?;
+    config::save_config(&config)?;
 
     Ok(())
 }
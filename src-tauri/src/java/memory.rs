@@ -0,0 +1,157 @@
+use anyhow::{Error, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{config::Config, instance::Java};
+
+const MINIMUM_HEAP: u32 = 512;
+const LARGE_HEAP_THRESHOLD: u32 = 12 * 1024;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MemorySettings {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl Default for MemorySettings {
+    fn default() -> Self {
+        Self {
+            min: MINIMUM_HEAP,
+            max: 2048,
+        }
+    }
+}
+
+impl MemorySettings {
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.max < MINIMUM_HEAP {
+            return Err(anyhow!(
+                "Maximum memory must be at least {} MB, got {} MB",
+                MINIMUM_HEAP,
+                self.max
+            ));
+        }
+
+        if self.min > self.max {
+            return Err(anyhow!(
+                "Minimum memory ({} MB) cannot be greater than maximum memory ({} MB)",
+                self.min,
+                self.max
+            ));
+        }
+
+        if let Some(total) = total_system_memory() {
+            if u64::from(self.max) > total {
+                return Err(anyhow!(
+                    "Maximum memory ({} MB) exceeds total system memory ({} MB)",
+                    self.max,
+                    total
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum JvmPreset {
+    #[default]
+    None,
+    Aikar,
+    Zgc,
+}
+
+pub fn build_jvm_args(java: &Java, config: &Config) -> Result<Vec<String>, Error> {
+    let memory = java.memory.unwrap_or(config.default_memory);
+    memory.validate()?;
+
+    // Aikar's flags are tuned for a heap that never resizes.
+    let min = match java.preset {
+        JvmPreset::Aikar => memory.max,
+        _ => memory.min,
+    };
+
+    let mut args = vec![format!("-Xms{}M", min), format!("-Xmx{}M", memory.max)];
+    args.extend(preset_args(java.preset, java.version, memory.max)?);
+    args.extend(
+        java.args
+            .iter()
+            .filter(|arg| !arg.trim().is_empty())
+            .cloned(),
+    );
+
+    Ok(args)
+}
+
+fn preset_args(preset: JvmPreset, java_version: u8, max_memory: u32) -> Result<Vec<String>, Error> {
+    let args = match preset {
+        JvmPreset::None => vec![],
+        JvmPreset::Aikar => aikar_flags(max_memory),
+        JvmPreset::Zgc if java_version < 17 => {
+            return Err(anyhow!(
+                "The ZGC preset requires Java 17 or newer, but the instance uses Java {}",
+                java_version
+            ));
+        }
+        JvmPreset::Zgc if java_version >= 21 => {
+            vec!["-XX:+UseZGC".to_string(), "-XX:+ZGenerational".to_string()]
+        }
+        JvmPreset::Zgc => vec!["-XX:+UseZGC".to_string()],
+    };
+
+    Ok(args)
+}
+
+fn aikar_flags(max_memory: u32) -> Vec<String> {
+    // https://docs.papermc.io/paper/aikars-flags, with the larger region and young
+    // generation sizes recommended for heaps of 12 GB and above.
+    let (new_size, max_new_size, region_size, reserve, occupancy) =
+        if max_memory >= LARGE_HEAP_THRESHOLD {
+            (40, 50, "16M", 15, 20)
+        } else {
+            (30, 40, "8M", 20, 15)
+        };
+
+    vec![
+        "-XX:+UseG1GC".to_string(),
+        "-XX:+ParallelRefProcEnabled".to_string(),
+        "-XX:MaxGCPauseMillis=200".to_string(),
+        "-XX:+UnlockExperimentalVMOptions".to_string(),
+        "-XX:+DisableExplicitGC".to_string(),
+        "-XX:+AlwaysPreTouch".to_string(),
+        format!("-XX:G1NewSizePercent={}", new_size),
+        format!("-XX:G1MaxNewSizePercent={}", max_new_size),
+        format!("-XX:G1HeapRegionSize={}", region_size),
+        format!("-XX:G1ReservePercent={}", reserve),
+        "-XX:G1HeapWastePercent=5".to_string(),
+        "-XX:G1MixedGCCountTarget=4".to_string(),
+        format!("-XX:InitiatingHeapOccupancyPercent={}", occupancy),
+        "-XX:G1MixedGCLiveThresholdPercent=90".to_string(),
+        "-XX:G1RSetUpdatingPauseTimePercent=5".to_string(),
+        "-XX:SurvivorRatio=32".to_string(),
+        "-XX:+PerfDisableSharedMem".to_string(),
+        "-XX:MaxTenuringThreshold=1".to_string(),
+    ]
+}
+
+#[cfg(target_os = "linux")]
+pub fn total_system_memory() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let total_kb = meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemTotal:"))?
+        .split_whitespace()
+        .next()?
+        .parse::<u64>()
+        .ok()?;
+
+    Some(total_kb / 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn total_system_memory() -> Option<u64> {
+    None
+}
@@ -2,5 +2,6 @@ pub mod config;
 pub mod detect;
 pub mod download;
 pub mod extract;
+pub mod memory;
 pub mod structs;
 pub mod test;
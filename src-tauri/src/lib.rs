@@ -1,10 +1,15 @@
-use std::{collections::HashMap, process, sync::Arc};
+use std::{collections::HashMap, sync::Arc};
 
 use auth::auth::LoginHandle;
 use discord_rich_presence::DiscordIpcClient;
 use instance::InstanceConfig;
 use log::{error, info};
 use reqwest::Client;
+use resources::{
+    launch::{GameProcess, StopMethod},
+    launch_state::LaunchStatesMap,
+    stats::StatsHistory,
+};
 use specta_typescript::{BigIntExportBehavior, Typescript};
 use tauri::{Manager, WindowEvent, Wry};
 use tauri_specta::{Builder, collect_commands, collect_events};
@@ -18,8 +23,17 @@ mod instance;
 mod java;
 mod resources;
 
-pub type ProcessHandle = Arc<Mutex<Option<process::Child>>>;
-pub type RunningInstancesMap = HashMap<String, ProcessHandle>;
+pub type ProcessHandle = Arc<Mutex<Option<GameProcess>>>;
+pub type RunningInstancesMap = HashMap<String, RunningInstance>;
+
+#[derive(Clone)]
+pub struct RunningInstance {
+    pub process: ProcessHandle,
+    pub stop_method: Arc<Mutex<Option<StopMethod>>>,
+    pub stats: StatsHistory,
+    // Whether this run holds a subscription on the instance file watcher.
+    pub watching: bool,
+}
 
 pub struct AppState {
     client: Arc<Mutex<Client>>,
@@ -27,6 +41,7 @@ pub struct AppState {
     login_handle: LoginHandle,
     discord_client: Arc<Mutex<Option<DiscordIpcClient>>>,
     running_instances: Arc<Mutex<RunningInstancesMap>>,
+    launch_states: Arc<std::sync::Mutex<LaunchStatesMap>>,
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -34,6 +49,8 @@ pub fn run() {
     let builder = Builder::<Wry>::new()
         .commands(collect_commands![
             commands::login,
+            commands::add_offline_account,
+            commands::login_yggdrasil,
             commands::cancel_login,
             commands::set_onboarding_complete,
             commands::switch_account,
@@ -46,13 +63,18 @@ pub fn run() {
             commands::test_java,
             commands::save_java_to_config,
             commands::get_java_from_config,
+            commands::get_system_memory,
             commands::get_instances,
             commands::get_instance,
             commands::create_instance,
             commands::update_instance,
             commands::delete_instance,
             commands::launch_instance,
+            commands::stop_instance,
             commands::kill_instance,
+            commands::get_instance_state,
+            commands::get_instance_stats,
+            commands::get_quick_play_history,
             commands::get_versions,
             commands::set_discord_activity,
             commands::toggle_discord_rpc,
@@ -60,16 +82,47 @@ pub fn run() {
             commands::save_launcher_settings,
             commands::get_avatar,
             commands::get_screenshots,
+            commands::get_screenshot_gallery,
+            commands::delete_screenshots,
+            commands::get_trashed_screenshots,
+            commands::restore_screenshot,
+            commands::empty_screenshot_trash,
+            commands::rename_screenshot,
+            commands::copy_screenshot_to_clipboard,
+            commands::export_screenshots,
+            commands::move_screenshots,
             commands::open_screenshots_dir,
             commands::watch_screenshots_for_instance,
             commands::stop_watching_screenshots,
+            commands::watch_instance_files,
+            commands::unwatch_instance_files,
             commands::get_worlds,
             commands::open_worlds_dir,
             commands::open_world_dir,
             commands::delete_world,
+            commands::get_world_players,
+            commands::get_player_inventory,
+            commands::import_world,
+            commands::export_world,
+            commands::duplicate_world,
+            commands::rename_world,
+            commands::copy_world_to_instance,
+            commands::backup_world,
+            commands::get_world_backups,
+            commands::restore_world_backup,
+            commands::delete_world_backup,
+            commands::prune_world_backups,
+            commands::get_servers,
+            commands::add_server,
+            commands::update_server,
+            commands::remove_server,
+            commands::move_server,
+            commands::copy_servers,
+            commands::ping_server,
         ])
         .events(collect_events![
             auth::auth::LoginDetailsEvent,
+            auth::auth::AccountNeedsReauthEvent,
             instance::InstanceListUpdatedEvent,
             java::download::JavaDownloadStartedEvent,
             java::download::JavaDownloadProgressEvent,
@@ -81,9 +134,13 @@ pub fn run() {
             resources::launch::AssetsDownloadStartedEvent,
             resources::launch::AssetsDownloadFinishedEvent,
             resources::launch::InstanceStartedEvent,
+            resources::launch::InstanceStoppingEvent,
             resources::launch::InstanceStoppedEvent,
             resources::launch::InstanceLogEvent,
+            resources::launch_state::InstanceStateChangedEvent,
+            resources::watcher::InstanceFilesChangedEvent,
             resources::screenshots::ScreenshotEvent,
+            resources::stats::InstanceStatsEvent,
         ]);
 
     #[cfg(debug_assertions)]
@@ -105,6 +162,11 @@ pub fn run() {
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_clipboard_manager::init())
+        .register_asynchronous_uri_scheme_protocol("screenshot", |_ctx, request, responder| {
+            tauri::async_runtime::spawn_blocking(move || {
+                responder.respond(resources::screenshots::serve_screenshot(&request))
+            });
+        })
         .manage({
             let client = Arc::new(Mutex::new(Client::new()));
             let instances = Arc::new(Mutex::new(
@@ -114,6 +176,7 @@ pub fn run() {
             let login_handle = LoginHandle::new();
             let discord_client = Arc::new(Mutex::new(None));
             let running_instances = Arc::new(Mutex::new(HashMap::new()));
+            let launch_states = Arc::new(std::sync::Mutex::new(HashMap::new()));
 
             AppState {
                 client,
@@ -121,6 +184,7 @@ pub fn run() {
                 login_handle,
                 discord_client,
                 running_instances,
+                launch_states,
             }
         })
         .invoke_handler(builder.invoke_handler())
@@ -150,6 +214,12 @@ pub fn run() {
                 }
             }
 
+            tauri::async_runtime::spawn(async {
+                if let Err(e) = auth::auth::migrate_credentials().await {
+                    error!("Failed to migrate account credentials: {:?}", e);
+                }
+            });
+
             let config = config::get_config()?;
             info!(
                 "Initial config loaded. Rich Presence enabled: {}",
@@ -185,10 +255,14 @@ pub fn run() {
                 }
             });
 
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(auth::auth::run_refresh_task(handle));
+
             let handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
-                let state = handle.state::<AppState>();
-                auth::auth::refresh(&*state.client.lock().await).await
+                if let Err(e) = resources::launch::reattach_instances(handle).await {
+                    error!("Failed to re-attach to running instances: {:?}", e);
+                }
             });
 
             Ok(())
@@ -0,0 +1,112 @@
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Error, Result, anyhow};
+use async_zip::{
+    Compression, ZipEntryBuilder,
+    tokio::{read::seek::ZipFileReader, write::ZipFileWriter},
+};
+use tokio::{
+    fs::{self, File},
+    io::{self, BufReader},
+};
+use tokio_util::compat::{FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt};
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+use crate::config;
+
+// Archives and extracted worlds are assembled here and only renamed into place once
+// complete, so neither the game nor the file watcher sees them half-written.
+pub async fn get_staging_path(suffix: &str) -> Result<PathBuf, Error> {
+    let staging_dir = config::get_config_dir()?.join(".staging");
+    fs::create_dir_all(&staging_dir)
+        .await
+        .with_context(|| format!("Failed to create staging directory {:?}", staging_dir))?;
+
+    Ok(staging_dir.join(format!("{}{}", Uuid::new_v4(), suffix)))
+}
+
+pub async fn zip_dir(
+    source_dir: &Path,
+    archive_path: &Path,
+    prefix: Option<&str>,
+    skipped_files: &[&str],
+) -> Result<(), Error> {
+    let files = WalkDir::new(source_dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| !skipped_files.contains(&entry.file_name().to_string_lossy().as_ref()))
+        .map(|entry| {
+            let path = entry.into_path();
+            let relative_path = path.strip_prefix(source_dir)?;
+            let mut entry_name = relative_path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            if let Some(prefix) = prefix {
+                entry_name = format!("{}/{}", prefix, entry_name);
+            }
+
+            Ok((path, entry_name))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    zip_files(&files, archive_path).await
+}
+
+pub async fn zip_files(files: &[(PathBuf, String)], archive_path: &Path) -> Result<(), Error> {
+    let archive = File::create(archive_path)
+        .await
+        .with_context(|| format!("Failed to create archive {:?}", archive_path))?;
+    let mut writer = ZipFileWriter::with_tokio(archive);
+
+    for (path, entry_name) in files {
+        let entry = ZipEntryBuilder::new(entry_name.clone().into(), Compression::Deflate);
+        let mut entry_writer = writer.write_entry_stream(entry).await?;
+        let mut file = File::open(path)
+            .await
+            .with_context(|| format!("Failed to open {:?}", path))?
+            .compat();
+        futures::io::copy(&mut file, &mut entry_writer).await?;
+        entry_writer.close().await?;
+    }
+
+    writer.close().await?;
+    Ok(())
+}
+
+pub async fn extract_zip(archive_path: &Path, output_dir: &Path) -> Result<(), Error> {
+    let file = File::open(archive_path)
+        .await
+        .with_context(|| format!("Failed to open archive {:?}", archive_path))?;
+    let mut archive = ZipFileReader::with_tokio(BufReader::new(file)).await?;
+    let entries = archive.file().entries().to_vec();
+
+    for (index, entry) in entries.iter().enumerate() {
+        let entry_path = PathBuf::from(entry.filename().as_str()?);
+        if entry_path
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            return Err(anyhow!("Invalid zip path detected"));
+        }
+
+        let output_path = output_dir.join(entry_path);
+        if entry.dir()? {
+            fs::create_dir_all(&output_path).await?;
+            continue;
+        }
+
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let mut output_file = File::create(&output_path).await?;
+        let mut entry_reader = archive.reader_with_entry(index).await?.compat();
+        io::copy(&mut entry_reader, &mut output_file).await?;
+    }
+
+    fs::create_dir_all(output_dir).await?;
+    Ok(())
+}
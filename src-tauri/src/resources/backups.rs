@@ -0,0 +1,365 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Error, Result, anyhow};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+use tokio::fs;
+
+use crate::{
+    AppState, config,
+    resources::{
+        archive,
+        worlds::{self, SKIPPED_FILES},
+    },
+};
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d_%H-%M-%S-%3f";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupSettings {
+    #[serde(default)]
+    pub auto_backup: bool,
+    #[serde(default = "default_keep_last")]
+    pub keep_last: Option<u32>,
+    #[serde(default)]
+    pub max_age_days: Option<u32>,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self {
+            auto_backup: false,
+            keep_last: default_keep_last(),
+            max_age_days: None,
+        }
+    }
+}
+
+fn default_keep_last() -> Option<u32> {
+    Some(10)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldBackup {
+    pub id: String,
+    pub world: String,
+    pub path: PathBuf,
+    pub size: u64,
+    #[specta(type = String)]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum RestoreTarget {
+    NewFolder,
+    Replace,
+}
+
+fn get_saves_dir(slug: &str) -> Result<PathBuf, Error> {
+    let config_dir = config::get_config_dir()?;
+    Ok(config_dir.join("instances").join(slug).join("saves"))
+}
+
+fn get_backups_dir(slug: &str) -> Result<PathBuf, Error> {
+    let config_dir = config::get_config_dir()?;
+    Ok(config_dir.join("backups").join(slug))
+}
+
+async fn get_backup_settings(
+    state: &State<'_, AppState>,
+    slug: &str,
+) -> Result<BackupSettings, Error> {
+    let instances_config = state.instances.lock().await;
+    let instance = instances_config
+        .get_instance(slug)
+        .ok_or_else(|| anyhow!("Instance {} not found", slug))?;
+
+    Ok(instance.settings.backups)
+}
+
+pub async fn backup_world(
+    state: &State<'_, AppState>,
+    slug: &str,
+    world: &str,
+) -> Result<WorldBackup, Error> {
+    let backup = create_backup(slug, world).await?;
+
+    let settings = get_backup_settings(state, slug).await?;
+    if let Err(e) = prune(slug, world, &settings, None).await {
+        warn!(
+            "Failed to prune backups for world {} in {}: {}",
+            world, slug, e
+        );
+    }
+
+    Ok(backup)
+}
+
+pub async fn backup_all_worlds(state: &State<'_, AppState>, slug: &str) -> Result<(), Error> {
+    let saves_dir = get_saves_dir(slug)?;
+    if !saves_dir.exists() {
+        return Ok(());
+    }
+
+    let mut entries = fs::read_dir(&saves_dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if !entry.path().join("level.dat").exists() {
+            continue;
+        }
+
+        let world = entry.file_name().to_string_lossy().to_string();
+        if let Err(e) = backup_world(state, slug, &world).await {
+            warn!("Failed to back up world {} in {}: {}", world, slug, e);
+        }
+    }
+
+    Ok(())
+}
+
+async fn create_backup(slug: &str, world: &str) -> Result<WorldBackup, Error> {
+    worlds::validate_folder_name(world)?;
+
+    let world_dir = get_saves_dir(slug)?.join(world);
+    if !world_dir.is_dir() {
+        return Err(anyhow!("World directory does not exist: {:?}", world_dir));
+    }
+
+    let backup_dir = get_backups_dir(slug)?.join(world);
+    fs::create_dir_all(&backup_dir)
+        .await
+        .with_context(|| format!("Failed to create backup directory {:?}", backup_dir))?;
+
+    let created_at = Utc::now();
+    let id = created_at.format(TIMESTAMP_FORMAT).to_string();
+    let backup_path = backup_dir.join(format!("{}.zip", id));
+    let temp_path = archive::get_staging_path(".zip").await?;
+
+    info!(
+        "Backing up world {} of {} to {:?}",
+        world, slug, backup_path
+    );
+
+    if let Err(e) = archive::zip_dir(&world_dir, &temp_path, None, SKIPPED_FILES).await {
+        let _ = fs::remove_file(&temp_path).await;
+        return Err(e.context(format!("Failed to back up world {}", world)));
+    }
+    fs::rename(&temp_path, &backup_path).await?;
+
+    let size = fs::metadata(&backup_path).await?.len();
+    Ok(WorldBackup {
+        id,
+        world: world.to_string(),
+        path: backup_path,
+        size,
+        created_at,
+    })
+}
+
+pub async fn get_backups(slug: &str, world: Option<&str>) -> Result<Vec<WorldBackup>, Error> {
+    let backups_dir = get_backups_dir(slug)?;
+    let world_dirs = match world {
+        Some(world) => {
+            worlds::validate_folder_name(world)?;
+            vec![backups_dir.join(world)]
+        }
+        None if backups_dir.exists() => {
+            let mut dirs = Vec::new();
+            let mut entries = fs::read_dir(&backups_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                if entry.file_type().await?.is_dir() {
+                    dirs.push(entry.path());
+                }
+            }
+            dirs
+        }
+        None => vec![],
+    };
+
+    let mut backups = Vec::new();
+    for world_dir in world_dirs {
+        if !world_dir.exists() {
+            continue;
+        }
+
+        let world = world_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut entries = fs::read_dir(&world_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("zip") {
+                continue;
+            }
+
+            let Some(id) = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+            else {
+                continue;
+            };
+            let metadata = entry.metadata().await?;
+            let created_at = match NaiveDateTime::parse_from_str(&id, TIMESTAMP_FORMAT) {
+                Ok(timestamp) => timestamp.and_utc(),
+                Err(_) => DateTime::<Utc>::from(metadata.modified()?),
+            };
+
+            backups.push(WorldBackup {
+                id,
+                world: world.clone(),
+                path,
+                size: metadata.len(),
+                created_at,
+            });
+        }
+    }
+
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(backups)
+}
+
+fn get_backup_path(slug: &str, world: &str, id: &str) -> Result<PathBuf, Error> {
+    worlds::validate_folder_name(world)?;
+    worlds::validate_folder_name(id)?;
+
+    let backup_path = get_backups_dir(slug)?
+        .join(world)
+        .join(format!("{}.zip", id));
+    if !backup_path.is_file() {
+        return Err(anyhow!("Backup {} of world {} does not exist", id, world));
+    }
+
+    Ok(backup_path)
+}
+
+pub async fn delete_backup(slug: &str, world: &str, id: &str) -> Result<(), Error> {
+    let backup_path = get_backup_path(slug, world, id)?;
+    fs::remove_file(&backup_path)
+        .await
+        .with_context(|| format!("Failed to delete backup {:?}", backup_path))?;
+
+    Ok(())
+}
+
+pub async fn prune_backups(
+    state: &State<'_, AppState>,
+    slug: &str,
+    world: &str,
+) -> Result<usize, Error> {
+    let settings = get_backup_settings(state, slug).await?;
+    prune(slug, world, &settings, None).await
+}
+
+// `keep_id` is spared regardless of the policy, e.g. the backup that was just restored.
+async fn prune(
+    slug: &str,
+    world: &str,
+    settings: &BackupSettings,
+    keep_id: Option<&str>,
+) -> Result<usize, Error> {
+    let backups = get_backups(slug, Some(world)).await?;
+    let cutoff = settings
+        .max_age_days
+        .map(|days| Utc::now() - Duration::days(i64::from(days)));
+
+    let mut removed = 0;
+    // The newest backup is never pruned, whatever the policy says.
+    for (index, backup) in backups.iter().enumerate().skip(1) {
+        if keep_id == Some(backup.id.as_str()) {
+            continue;
+        }
+
+        let over_count = settings
+            .keep_last
+            .is_some_and(|keep_last| index >= keep_last as usize);
+        let too_old = cutoff.is_some_and(|cutoff| backup.created_at < cutoff);
+
+        if over_count || too_old {
+            fs::remove_file(&backup.path)
+                .await
+                .with_context(|| format!("Failed to delete backup {:?}", backup.path))?;
+            removed += 1;
+        }
+    }
+
+    if removed > 0 {
+        info!("Pruned {} backups of world {} in {}", removed, world, slug);
+    }
+
+    Ok(removed)
+}
+
+pub async fn restore_backup(
+    state: &State<'_, AppState>,
+    slug: &str,
+    world: &str,
+    id: &str,
+    target: RestoreTarget,
+) -> Result<String, Error> {
+    worlds::ensure_not_running(state, slug, "restore backup").await?;
+
+    let backup_path = get_backup_path(slug, world, id)?;
+    let saves_dir = get_saves_dir(slug)?;
+    fs::create_dir_all(&saves_dir).await?;
+
+    let staging_dir = archive::get_staging_path("").await?;
+    if let Err(e) = archive::extract_zip(&backup_path, &staging_dir).await {
+        let _ = fs::remove_dir_all(&staging_dir).await;
+        return Err(e.context(format!("Failed to extract backup {}", id)));
+    }
+
+    let world_dir = saves_dir.join(world);
+    let mut took_safety_backup = false;
+    let folder_name = match target {
+        RestoreTarget::Replace => {
+            if world_dir.exists() {
+                info!("Taking a safety backup of {} before restoring", world);
+                // Not pruned until the restore succeeded, pruning could delete the
+                // backup being restored.
+                if let Err(e) = create_backup(slug, world).await {
+                    let _ = fs::remove_dir_all(&staging_dir).await;
+                    return Err(e.context("Failed to take a safety backup before restoring"));
+                }
+                took_safety_backup = true;
+                fs::remove_dir_all(&world_dir)
+                    .await
+                    .with_context(|| format!("Failed to remove world directory {:?}", world_dir))?;
+            }
+            world.to_string()
+        }
+        RestoreTarget::NewFolder => {
+            worlds::unique_folder_name(&saves_dir, &format!("{} (restored)", world))
+        }
+    };
+
+    if let Err(e) = fs::rename(&staging_dir, saves_dir.join(&folder_name)).await {
+        let _ = fs::remove_dir_all(&staging_dir).await;
+        return Err(Error::from(e).context(format!("Failed to restore backup {}", id)));
+    }
+    info!(
+        "Restored backup {} of world {} in {} to {}",
+        id, world, slug, folder_name
+    );
+
+    if took_safety_backup {
+        let pruned = async {
+            let settings = get_backup_settings(state, slug).await?;
+            prune(slug, world, &settings, Some(id)).await
+        }
+        .await;
+        if let Err(e) = pruned {
+            warn!(
+                "Failed to prune backups for world {} in {}: {}",
+                world, slug, e
+            );
+        }
+    }
+
+    Ok(folder_name)
+}
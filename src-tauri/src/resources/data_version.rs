@@ -0,0 +1,143 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use anyhow::{Error, Result};
+use async_zip::tokio::read::seek::ZipFileReader;
+use futures::AsyncReadExt;
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use tokio::{fs::File, io::BufReader};
+
+use crate::config;
+
+static DATA_VERSION_CACHE: Lazy<Mutex<HashMap<String, i32>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Release data versions, used when the client jar is not downloaded yet or predates
+// the `version.json` it ships since 1.14.
+const DATA_VERSIONS: &[(&str, i32)] = &[
+    ("1.9", 169),
+    ("1.9.1", 175),
+    ("1.9.2", 176),
+    ("1.9.3", 183),
+    ("1.9.4", 184),
+    ("1.10", 510),
+    ("1.10.1", 511),
+    ("1.10.2", 512),
+    ("1.11", 819),
+    ("1.11.1", 921),
+    ("1.11.2", 922),
+    ("1.12", 1139),
+    ("1.12.1", 1241),
+    ("1.12.2", 1343),
+    ("1.13", 1519),
+    ("1.13.1", 1628),
+    ("1.13.2", 1631),
+    ("1.14", 1952),
+    ("1.14.1", 1957),
+    ("1.14.2", 1963),
+    ("1.14.3", 1968),
+    ("1.14.4", 1976),
+    ("1.15", 2225),
+    ("1.15.1", 2227),
+    ("1.15.2", 2230),
+    ("1.16", 2566),
+    ("1.16.1", 2567),
+    ("1.16.2", 2578),
+    ("1.16.3", 2580),
+    ("1.16.4", 2584),
+    ("1.16.5", 2586),
+    ("1.17", 2724),
+    ("1.17.1", 2730),
+    ("1.18", 2860),
+    ("1.18.1", 2865),
+    ("1.18.2", 2975),
+    ("1.19", 3105),
+    ("1.19.1", 3117),
+    ("1.19.2", 3120),
+    ("1.19.3", 3218),
+    ("1.19.4", 3337),
+    ("1.20", 3463),
+    ("1.20.1", 3465),
+    ("1.20.2", 3578),
+    ("1.20.3", 3698),
+    ("1.20.4", 3700),
+    ("1.20.5", 3837),
+    ("1.20.6", 3839),
+    ("1.21", 3953),
+    ("1.21.1", 3955),
+    ("1.21.2", 4080),
+    ("1.21.3", 4082),
+    ("1.21.4", 4189),
+    ("1.21.5", 4325),
+    ("1.21.6", 4435),
+    ("1.21.7", 4438),
+    ("1.21.8", 4440),
+];
+
+#[derive(Deserialize, Debug)]
+struct JarVersion {
+    world_version: i32,
+}
+
+pub async fn get_data_version(game_version: &str) -> Option<i32> {
+    if let Some(data_version) = DATA_VERSION_CACHE.lock().unwrap().get(game_version) {
+        return Some(*data_version);
+    }
+
+    let data_version = match read_from_jar(game_version).await {
+        Ok(Some(data_version)) => Some(data_version),
+        Ok(None) => lookup(game_version),
+        Err(e) => {
+            warn!(
+                "Failed to read data version from {} jar: {}",
+                game_version, e
+            );
+            lookup(game_version)
+        }
+    }?;
+
+    DATA_VERSION_CACHE
+        .lock()
+        .unwrap()
+        .insert(game_version.to_string(), data_version);
+    Some(data_version)
+}
+
+fn lookup(game_version: &str) -> Option<i32> {
+    DATA_VERSIONS
+        .iter()
+        .find(|(version, _)| *version == game_version)
+        .map(|(_, data_version)| *data_version)
+}
+
+async fn read_from_jar(game_version: &str) -> Result<Option<i32>, Error> {
+    let jar_path = config::get_config_dir()?
+        .join("versions")
+        .join(game_version)
+        .join(format!("{}.jar", game_version));
+    if !jar_path.exists() {
+        return Ok(None);
+    }
+
+    let file = File::open(&jar_path).await?;
+    let mut archive = ZipFileReader::with_tokio(BufReader::new(file)).await?;
+    let Some(index) = archive.file().entries().iter().position(|entry| {
+        entry
+            .filename()
+            .as_str()
+            .is_ok_and(|name| name == "version.json")
+    }) else {
+        return Ok(None);
+    };
+
+    let mut contents = String::new();
+    archive
+        .reader_with_entry(index)
+        .await?
+        .read_to_string(&mut contents)
+        .await?;
+    let version = serde_json::from_str::<JarVersion>(&contents)?;
+
+    Ok(Some(version.world_version))
+}
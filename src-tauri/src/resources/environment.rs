@@ -0,0 +1,99 @@
+use std::{collections::BTreeMap, env, process::Command};
+
+use log::info;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[cfg(target_os = "windows")]
+const PRESERVED_VARIABLES: &[&str] = &[
+    "SystemRoot",
+    "SystemDrive",
+    "windir",
+    "TEMP",
+    "TMP",
+    "USERPROFILE",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "PATH",
+];
+
+#[cfg(not(target_os = "windows"))]
+const PRESERVED_VARIABLES: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "LANG",
+    "DISPLAY",
+    "XAUTHORITY",
+    "WAYLAND_DISPLAY",
+    "XDG_RUNTIME_DIR",
+    "XDG_SESSION_TYPE",
+    "DBUS_SESSION_BUS_ADDRESS",
+    "PULSE_SERVER",
+    "LD_LIBRARY_PATH",
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchEnvironment {
+    #[serde(default)]
+    pub wrapper: Vec<String>,
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+    #[serde(default)]
+    pub clear_inherited: bool,
+}
+
+pub fn wrapped_command(
+    program: &str,
+    global: &LaunchEnvironment,
+    instance: &LaunchEnvironment,
+) -> Command {
+    let mut argv = global
+        .wrapper
+        .iter()
+        .chain(instance.wrapper.iter())
+        .map(|arg| arg.trim())
+        .filter(|arg| !arg.is_empty());
+
+    match argv.next() {
+        Some(wrapper) => {
+            info!("Launching {} through wrapper {}", program, wrapper);
+            let mut command = Command::new(wrapper);
+            command.args(argv).arg(program);
+            command
+        }
+        None => Command::new(program),
+    }
+}
+
+pub fn clear_inherited(
+    command: &mut Command,
+    global: &LaunchEnvironment,
+    instance: &LaunchEnvironment,
+) {
+    if !global.clear_inherited && !instance.clear_inherited {
+        return;
+    }
+
+    // Only the variables needed to open a window and find libraries survive, so the
+    // game starts from a predictable environment rather than the launcher's own.
+    command.env_clear();
+    for key in PRESERVED_VARIABLES {
+        if let Ok(value) = env::var(key) {
+            command.env(key, value);
+        }
+    }
+}
+
+pub fn apply_variables(
+    command: &mut Command,
+    global: &LaunchEnvironment,
+    instance: &LaunchEnvironment,
+) {
+    for (key, value) in global.variables.iter().chain(instance.variables.iter()) {
+        if !key.trim().is_empty() {
+            command.env(key.trim(), value);
+        }
+    }
+}
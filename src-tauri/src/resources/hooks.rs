@@ -0,0 +1,263 @@
+use std::{path::Path, process::Stdio, time::Duration};
+
+use anyhow::{Error, Result, anyhow};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::AppHandle;
+use tauri_specta::Event;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, BufReader},
+    process::{Child, Command},
+};
+
+use crate::{instance::Instance, resources::launch::InstanceLogEvent};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchHooks {
+    #[serde(default)]
+    pub pre_launch: Option<String>,
+    #[serde(default)]
+    pub post_exit: Option<String>,
+    // Seconds a hook may run before it is killed, 0 lets it run without a limit.
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+    #[serde(default)]
+    pub abort_on_failure: bool,
+}
+
+impl Default for LaunchHooks {
+    fn default() -> Self {
+        Self {
+            pre_launch: None,
+            post_exit: None,
+            timeout: default_timeout(),
+            abort_on_failure: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum HookKind {
+    PreLaunch,
+    PostExit,
+}
+
+impl HookKind {
+    fn label(&self) -> &'static str {
+        match self {
+            HookKind::PreLaunch => "pre-launch",
+            HookKind::PostExit => "post-exit",
+        }
+    }
+}
+
+fn default_timeout() -> u64 {
+    60
+}
+
+pub async fn run_pre_launch(
+    handle: &AppHandle,
+    instance: &Instance,
+    instance_dir: &Path,
+) -> Result<(), Error> {
+    let hooks = &instance.settings.hooks;
+    let Some(command) = hooks.pre_launch.as_deref().filter(|c| !c.trim().is_empty()) else {
+        return Ok(());
+    };
+
+    match run_hook(
+        handle,
+        HookKind::PreLaunch,
+        command,
+        instance,
+        instance_dir,
+        None,
+    )
+    .await
+    {
+        Err(e) if hooks.abort_on_failure => Err(anyhow!("Launch aborted: {}", e)),
+        Err(e) => {
+            warn!(
+                "Ignoring failed pre-launch hook for {}: {}",
+                instance.slug, e
+            );
+            Ok(())
+        }
+        Ok(()) => Ok(()),
+    }
+}
+
+pub async fn run_post_exit(
+    handle: &AppHandle,
+    instance: &Instance,
+    instance_dir: &Path,
+    exit_code: Option<i32>,
+) {
+    let hooks = &instance.settings.hooks;
+    let Some(command) = hooks.post_exit.as_deref().filter(|c| !c.trim().is_empty()) else {
+        return;
+    };
+
+    if let Err(e) = run_hook(
+        handle,
+        HookKind::PostExit,
+        command,
+        instance,
+        instance_dir,
+        exit_code,
+    )
+    .await
+    {
+        warn!("Post-exit hook for {} failed: {}", instance.slug, e);
+    }
+}
+
+async fn run_hook(
+    handle: &AppHandle,
+    kind: HookKind,
+    command_line: &str,
+    instance: &Instance,
+    instance_dir: &Path,
+    exit_code: Option<i32>,
+) -> Result<(), Error> {
+    let label = kind.label();
+    let timeout = match instance.settings.hooks.timeout {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    };
+    info!(
+        "Running {} hook for {}: {}",
+        label, instance.slug, command_line
+    );
+
+    let mut command = shell_command(command_line);
+    command
+        .current_dir(instance_dir)
+        .env("INST_ID", &instance.slug)
+        .env("INST_NAME", &instance.name)
+        .env("INST_DIR", instance_dir)
+        .env("INST_JAVA", &instance.java.path)
+        .env("INST_VERSION", &instance.game.version)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    if let Some(code) = exit_code {
+        command.env("EXIT_CODE", code.to_string());
+    }
+
+    // A group of its own lets a timeout also end anything the hook started.
+    #[cfg(not(target_os = "windows"))]
+    command.process_group(0);
+
+    let mut child = command
+        .spawn()
+        .map_err(|e| anyhow!("Failed to start {} hook: {}", label, e))?;
+
+    let formatted_slug = instance.slug.replace(".", "_");
+    let stdout_task = child
+        .stdout
+        .take()
+        .map(|stdout| stream_output(handle.clone(), formatted_slug.clone(), label, stdout));
+    let stderr_task = child
+        .stderr
+        .take()
+        .map(|stderr| stream_output(handle.clone(), formatted_slug.clone(), label, stderr));
+
+    let waited = match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, child.wait()).await,
+        None => Ok(child.wait().await),
+    };
+    let status = match waited {
+        Ok(status) => status.map_err(|e| anyhow!("Failed to wait for {} hook: {}", label, e))?,
+        Err(_) => {
+            if let Err(e) = kill_hook(&mut child).await {
+                error!("Failed to kill timed out {} hook: {}", label, e);
+            }
+            return Err(anyhow!(
+                "The {} hook timed out after {} seconds",
+                label,
+                instance.settings.hooks.timeout
+            ));
+        }
+    };
+
+    // Background processes started by the hook can keep the pipes open, so output
+    // that arrives after the hook itself exited is only waited on briefly.
+    for task in [stdout_task, stderr_task].into_iter().flatten() {
+        let _ = tokio::time::timeout(Duration::from_secs(1), task).await;
+    }
+
+    if !status.success() {
+        return Err(anyhow!("The {} hook exited with status: {}", label, status));
+    }
+
+    info!(
+        "The {} hook for {} finished successfully",
+        label, instance.slug
+    );
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+async fn kill_hook(child: &mut Child) -> std::io::Result<()> {
+    child.kill().await
+}
+
+#[cfg(not(target_os = "windows"))]
+async fn kill_hook(child: &mut Child) -> std::io::Result<()> {
+    if let Some(pid) = child.id() {
+        unsafe { libc::kill(-(pid as libc::pid_t), libc::SIGKILL) };
+    }
+    child.kill().await
+}
+
+#[cfg(target_os = "windows")]
+fn shell_command(command_line: &str) -> Command {
+    let mut command = Command::new("cmd");
+    command.arg("/C").arg(command_line);
+    command
+}
+
+#[cfg(not(target_os = "windows"))]
+fn shell_command(command_line: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(command_line);
+    command
+}
+
+fn stream_output<R>(
+    handle: AppHandle,
+    slug: String,
+    label: &'static str,
+    output: R,
+) -> tokio::task::JoinHandle<()>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(output).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let line = format!("[{}] {}", label, line);
+            if let Err(e) = (InstanceLogEvent {
+                slug: &slug,
+                line: &line,
+            })
+            .emit(&handle)
+            {
+                error!(
+                    "Failed to emit {} hook log event for {}: {}",
+                    label, slug, e
+                );
+            }
+        }
+    })
+}
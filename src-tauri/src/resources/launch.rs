@@ -1,33 +1,44 @@
 use std::{
     io::{BufRead, BufReader},
     path::Path,
-    process::{Command, ExitStatus, Stdio},
+    process::{Child, Command, ExitStatus, Stdio},
     sync::Arc,
     thread,
     time::Duration,
 };
 
 use anyhow::{Error, Result, anyhow};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use discord_rich_presence::DiscordIpcClient;
 use futures::try_join;
 use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 use specta::Type;
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State, async_runtime::JoinHandle};
 use tauri_specta::Event;
 use tokio::{sync::Mutex, time::Instant};
 use walkdir::WalkDir;
 
 use crate::{
-    AppState, ProcessHandle, RunningInstancesMap,
-    auth::account::Account,
+    AppState, ProcessHandle, RunningInstance, RunningInstancesMap,
+    auth::{
+        self,
+        account::{Credentials, Session},
+        yggdrasil,
+    },
     config::{self, Config},
     discord,
     instance::Instance,
+    java::memory,
     resources::{
         assets::AssetManager,
+        backups, environment, hooks,
+        launch_state::{self, LaunchError, LaunchGuard, LaunchState},
+        quick_play::{self, QuickPlayTarget},
+        run_registry::{self, RunRecord},
+        stats,
         version::{VersionManifest, get_version_manifest},
+        watcher, worlds,
     },
 };
 
@@ -46,10 +57,27 @@ pub struct InstanceStartedEvent<'a> {
     pub message: &'a str,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum StopMethod {
+    Exited,
+    Terminated,
+    Killed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type, Event)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceStoppingEvent<'a> {
+    pub slug: &'a str,
+    pub elapsed: u64,
+    pub grace_period: u64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Type, Event)]
 pub struct InstanceStoppedEvent<'a> {
     pub slug: &'a str,
     pub message: &'a str,
+    pub method: StopMethod,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Type, Event)]
@@ -58,6 +86,31 @@ pub struct InstanceLogEvent<'a> {
     pub line: &'a str,
 }
 
+pub enum GameProcess {
+    Spawned(Child),
+    Adopted(RunRecord),
+}
+
+impl GameProcess {
+    pub fn id(&self) -> u32 {
+        match self {
+            GameProcess::Spawned(child) => child.id(),
+            GameProcess::Adopted(record) => record.pid,
+        }
+    }
+
+    fn try_wait(&mut self) -> Result<Option<ExitStatus>, Error> {
+        match self {
+            GameProcess::Spawned(child) => Ok(child.try_wait()?),
+            GameProcess::Adopted(record) if record.is_alive() => Ok(None),
+            GameProcess::Adopted(_) => success_exit_status().map(Some),
+        }
+    }
+}
+
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const FORCE_KILL_TIMEOUT: Duration = Duration::from_secs(5);
+
 async fn download_instance_assets(
     state: &State<'_, AppState>,
     handle: &AppHandle,
@@ -125,9 +178,24 @@ pub async fn launch(
     state: State<'_, AppState>,
     handle: AppHandle,
     slug: &str,
+    target: Option<QuickPlayTarget>,
+    allow_downgrade: bool,
 ) -> Result<(), Error> {
     info!("Launching instance: {}", slug);
 
+    // A game that outlived the previous launcher session may not be re-attached yet.
+    // Checked before taking the launch state so re-attaching it is never blocked.
+    match run_registry::get_record(slug) {
+        Ok(Some(record)) if record.is_alive() => {
+            return Err(LaunchError::AlreadyRunning(slug.to_string()).into());
+        }
+        Ok(_) => {}
+        Err(e) => warn!("Failed to check the run registry for {}: {}", slug, e),
+    }
+
+    let launch_guard =
+        LaunchGuard::acquire(&state.launch_states, &handle, slug, LaunchState::Preparing)?;
+
     let config_dir = config::get_config_dir()?;
     let discord_client_state = &state.discord_client;
     let running_instances_map = state.running_instances.clone();
@@ -145,6 +213,7 @@ pub async fn launch(
     };
 
     if needs_asset_download {
+        launch_guard.set(LaunchState::Downloading);
         download_instance_assets(&state, &handle, slug, &version_manifest, &config_dir).await?;
     }
 
@@ -158,6 +227,14 @@ pub async fn launch(
         (instance, dir)
     };
 
+    if !Path::new(&instance_game_launch.java.path).is_file() {
+        return Err(anyhow!(
+            "Java executable for instance {} not found at {}",
+            slug,
+            instance_game_launch.java.path
+        ));
+    }
+
     if !instance_dir.exists() {
         tokio::fs::create_dir_all(&instance_dir)
             .await
@@ -170,40 +247,78 @@ pub async fn launch(
             })?;
     }
 
+    if let Some(QuickPlayTarget::Singleplayer { world }) = &target {
+        if !allow_downgrade {
+            worlds::check_downgrade(slug, world, &instance_game_launch.game.version).await?;
+        }
+    }
+
+    let quick_play_args = match &target {
+        Some(target) => quick_play::game_args(target, &version_manifest, &instance_dir)?,
+        None => vec![],
+    };
+
+    if instance_game_launch.settings.backups.auto_backup {
+        launch_guard.set(LaunchState::BackingUp);
+        if let Err(e) = backups::backup_all_worlds(&state, slug).await {
+            warn!("Failed to back up worlds before launching {}: {}", slug, e);
+        }
+    }
+
+    hooks::run_pre_launch(&handle, &instance_game_launch, &instance_dir).await?;
+
+    if let Some(target) = &target {
+        info!("Launching {} with quick play target {:?}", slug, target);
+        if let Err(e) = quick_play::record_history(slug, target) {
+            warn!("Failed to record quick play history for {}: {}", slug, e);
+        }
+    }
+
+    launch_guard.set(LaunchState::Starting);
     let start_time = Instant::now();
 
     let launch_game_result = launch_game(
         &instance_game_launch,
         &instance_dir,
         &version_manifest,
+        &quick_play_args,
         &handle,
         discord_client_state,
         running_instances_map,
+        &launch_guard,
     )
     .await;
 
     info!("Stopped instance: {}", slug);
 
     let duration_played = start_time.elapsed().as_secs();
-    let current_time = Utc::now();
+    record_play_time(&state, &handle, slug, duration_played, Utc::now()).await?;
 
-    {
-        let mut instances_config = state.instances.lock().await;
-        let mut instance_to_update = instances_config
-            .get_instance(slug)
-            .ok_or_else(|| anyhow!("Instance {} not found for time played update", slug))?;
+    launch_game_result?;
 
-        instance_to_update.settings.time_played += duration_played;
-        instance_to_update.settings.last_played = Some(current_time);
+    Ok(())
+}
 
-        if !instance_to_update.settings.has_launched {
-            instance_to_update.settings.has_launched = true;
-        }
+async fn record_play_time(
+    state: &State<'_, AppState>,
+    handle: &AppHandle,
+    slug: &str,
+    duration_played: u64,
+    last_played: DateTime<Utc>,
+) -> Result<(), Error> {
+    let mut instances_config = state.instances.lock().await;
+    let mut instance_to_update = instances_config
+        .get_instance(slug)
+        .ok_or_else(|| anyhow!("Instance {} not found for time played update", slug))?;
 
-        instances_config.update_instance(&handle, instance_to_update)?;
+    instance_to_update.settings.time_played += duration_played;
+    instance_to_update.settings.last_played = Some(last_played);
+
+    if !instance_to_update.settings.has_launched {
+        instance_to_update.settings.has_launched = true;
     }
 
-    launch_game_result?;
+    instances_config.update_instance(handle, instance_to_update)?;
 
     Ok(())
 }
@@ -212,15 +327,24 @@ fn prepare_game_args<'a>(
     instance: &'a Instance,
     instance_dir: &'a str,
     version_manifest: &'a VersionManifest,
-    account: &'a Account,
+    quick_play_args: &'a [String],
+    session: &'a Session,
     assets_dir: &'a str,
     width: &'a str,
     height: &'a str,
 ) -> Result<Vec<&'a str>, Error> {
-    let profile = &account.profile;
+    let profile = &session.account.profile;
     let version = &instance.game.version;
     let settings = &instance.settings;
 
+    let user_type = match &session.credentials {
+        Credentials::Microsoft(_) => "msa",
+        Credentials::Offline => "legacy",
+        Credentials::Yggdrasil(_) => "mojang",
+    };
+    // Offline accounts have no session, the game only needs a non-empty token.
+    let access_token = session.credentials.access_token().unwrap_or("0");
+
     #[rustfmt::skip]
     let mut game_args = vec![
         "--username", &profile.name,
@@ -229,8 +353,8 @@ fn prepare_game_args<'a>(
         "--assetsDir", assets_dir,
         "--assetIndex", &version_manifest.asset_index.id,
         "--uuid", &profile.id,
-        "--accessToken", &account.access_token,
-        "--userType", "msa",
+        "--accessToken", access_token,
+        "--userType", user_type,
         "--versionType", "Glyph Launcher",
     ];
 
@@ -241,6 +365,8 @@ fn prepare_game_args<'a>(
         game_args.push(height);
     }
 
+    game_args.extend(quick_play_args.iter().map(String::as_str));
+
     Ok(game_args)
 }
 
@@ -249,17 +375,34 @@ fn configure_launch_command(
     instance_dir: &Path,
     main_class: &str,
     classpath: &str,
+    jvm_args: Vec<String>,
     game_args: Vec<&str>,
     config: &Config,
 ) -> Command {
-    let mut command = Command::new(&instance.java.path);
+    let global_environment = &config.launch_environment;
+    let instance_environment = &instance.settings.environment;
+
+    let mut command = environment::wrapped_command(
+        &instance.java.path,
+        global_environment,
+        instance_environment,
+    );
     command
         .current_dir(instance_dir)
+        .args(jvm_args)
         .arg("-cp")
         .arg(classpath)
         .arg(main_class)
         .args(game_args);
 
+    environment::clear_inherited(&mut command, global_environment, instance_environment);
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
     #[cfg(target_os = "windows")]
     {
         let gpu = if config.use_discrete_gpu {
@@ -287,6 +430,8 @@ fn configure_launch_command(
         }
     }
 
+    environment::apply_variables(&mut command, global_environment, instance_environment);
+
     command
 }
 
@@ -294,9 +439,11 @@ async fn launch_game(
     instance: &Instance,
     instance_dir_path: &Path,
     version_manifest: &VersionManifest,
+    quick_play_args: &[String],
     handle: &AppHandle,
     discord_client_state: &Arc<Mutex<Option<DiscordIpcClient>>>,
     running_instances_map: Arc<Mutex<RunningInstancesMap>>,
+    launch_guard: &LaunchGuard,
 ) -> Result<(), Error> {
     let config = config::get_config()?;
     let config_dir = config::get_config_dir()?;
@@ -317,11 +464,8 @@ async fn launch_game(
         }
     }
 
-    let account = config
-        .accounts
-        .iter()
-        .find(|acc| acc.active)
-        .ok_or_else(|| anyhow!("No active account found"))?;
+    let client = handle.state::<AppState>().client.lock().await.clone();
+    let session = auth::auth::get_launch_session(&client, handle).await?;
 
     let instance_dir = instance_dir_path.to_str().ok_or_else(|| {
         anyhow!(
@@ -340,24 +484,31 @@ async fn launch_game(
         instance,
         instance_dir,
         version_manifest,
-        account,
+        quick_play_args,
+        &session,
         assets_dir,
         &width,
         &height,
     )?;
 
+    let mut jvm_args = memory::build_jvm_args(&instance.java, &config)?;
+    if let Credentials::Yggdrasil(credentials) = &session.credentials {
+        jvm_args.extend(yggdrasil::jvm_args(&client, &credentials.api_root).await?);
+    }
+
     let mut command = configure_launch_command(
         instance,
         instance_dir_path,
         main_class,
         &classpath,
+        jvm_args,
         game_args,
         &config,
     );
 
     let formatted_slug = instance.slug.replace(".", "_");
 
-    let child = command
+    let mut child = command
         .stdout(Stdio::piped())
         .spawn()
         .map_err(|e| Error::msg(format!("Failed to launch game: {}", e)))?;
@@ -365,30 +516,49 @@ async fn launch_game(
     let process_id = child.id();
     info!("Launched game process with ID: {}", process_id);
 
-    let process_handle: ProcessHandle = Arc::new(Mutex::new(Some(child)));
-    {
-        let mut running_instances = running_instances_map.lock().await;
-        running_instances.insert(instance.slug.clone(), Arc::clone(&process_handle));
+    if let Err(e) = run_registry::add_record(RunRecord::new(&instance.slug, process_id)) {
+        warn!("Failed to record running instance {}: {}", instance.slug, e);
     }
 
+    let stdout = child.stdout.take();
+    let running_instance = track_process(
+        &running_instances_map,
+        handle,
+        &instance.slug,
+        GameProcess::Spawned(child),
+    )
+    .await;
+    launch_guard.set(LaunchState::Running);
+
+    let stats_sampler = stats::spawn_sampler(
+        handle.clone(),
+        instance.slug.clone(),
+        process_id,
+        Arc::clone(&running_instance.stats),
+    );
+
     InstanceStartedEvent {
         slug: &formatted_slug,
         message: "Game instance started",
     }
     .emit(handle)?;
 
-    let stdout = {
-        let mut child_opt = process_handle.lock().await;
-        child_opt.as_mut().and_then(|c| c.stdout.take())
-    };
-
     if let Some(stdout) = stdout {
         let stdout_handle = handle.clone();
         let log_slug = formatted_slug.clone();
+        let access_token = session
+            .credentials
+            .access_token()
+            .filter(|token| !token.is_empty())
+            .map(str::to_string);
         thread::spawn(move || {
             let reader = BufReader::new(stdout);
             for line in reader.lines().map_while(Result::ok) {
-                let line = line.trim().to_string();
+                let mut line = line.trim().to_string();
+                // Some loaders echo the game arguments, the token must not reach the webview.
+                if let Some(token) = access_token.as_deref() {
+                    line = line.replace(token, "<access token>");
+                }
                 if !line.is_empty() {
                     if let Err(e) = (InstanceLogEvent {
                         slug: &log_slug,
@@ -403,23 +573,60 @@ async fn launch_game(
         });
     }
 
-    let status = wait_for_process_completion(&process_handle).await?;
+    let status = wait_for_process_completion(&running_instance.process).await;
 
-    {
-        let mut child_opt = process_handle.lock().await;
-        *child_opt = None;
-    }
+    finish_instance(
+        handle,
+        instance,
+        instance_dir_path,
+        &running_instances_map,
+        &running_instance,
+        stats_sampler,
+        status.map(Some),
+    )
+    .await
+}
 
-    {
-        let mut running_instances = running_instances_map.lock().await;
-        running_instances.remove(&instance.slug);
+// Runs once the game process is gone, for launched and re-attached instances alike.
+// The exit status is `None` when the process was not spawned by this launcher.
+async fn finish_instance(
+    handle: &AppHandle,
+    instance: &Instance,
+    instance_dir: &Path,
+    running_instances_map: &Arc<Mutex<RunningInstancesMap>>,
+    running_instance: &RunningInstance,
+    stats_sampler: Option<JoinHandle<()>>,
+    status: Result<Option<ExitStatus>, Error>,
+) -> Result<(), Error> {
+    if let Some(sampler) = stats_sampler {
+        sampler.abort();
     }
 
-    InstanceStoppedEvent {
-        slug: &formatted_slug,
-        message: "Game instance stopped",
-    }
-    .emit(handle)?;
+    let stop_method = untrack_process(
+        running_instances_map,
+        &instance.slug,
+        running_instance,
+        handle,
+    )
+    .await?;
+    let status = status?;
+
+    // The game is gone but the instance is not free to launch again until the
+    // post-exit hook has run.
+    launch_state::set_state(
+        &handle.state::<AppState>().launch_states,
+        handle,
+        &instance.slug,
+        LaunchState::Finishing,
+    );
+
+    hooks::run_post_exit(
+        handle,
+        instance,
+        instance_dir,
+        status.and_then(|status| status.code()),
+    )
+    .await;
 
     #[cfg(target_os = "windows")]
     {
@@ -428,9 +635,9 @@ async fn launch_game(
         }
     }
 
-    if config.rich_presence {
+    if config::get_config()?.rich_presence {
         if let Err(e) = discord::set_activity(
-            discord_client_state,
+            &handle.state::<AppState>().discord_client,
             "Exploring the Launcher".to_string(),
             "Idle".to_string(),
         )
@@ -440,16 +647,197 @@ async fn launch_game(
         }
     }
 
-    if !status.success() {
-        return Err(Error::msg(format!(
-            "Game process exited with status: {}",
-            status
-        )));
+    match status {
+        Some(status) if !status.success() && stop_method == StopMethod::Exited => Err(Error::msg(
+            format!("Game process exited with status: {}", status),
+        )),
+        _ => Ok(()),
+    }
+}
+
+async fn track_process(
+    running_instances_map: &Arc<Mutex<RunningInstancesMap>>,
+    handle: &AppHandle,
+    slug: &str,
+    process: GameProcess,
+) -> RunningInstance {
+    // Worlds, screenshots, logs and crash reports change while the game runs.
+    let watching = match watcher::watch_instance(handle.clone(), slug) {
+        Ok(()) => true,
+        Err(e) => {
+            warn!("Failed to watch files of {}: {}", slug, e);
+            false
+        }
+    };
+
+    let running_instance = RunningInstance {
+        process: Arc::new(Mutex::new(Some(process))),
+        stop_method: Arc::new(Mutex::new(None)),
+        stats: stats::new_history(),
+        watching,
+    };
+
+    let mut running_instances = running_instances_map.lock().await;
+    running_instances.insert(slug.to_string(), running_instance.clone());
+
+    running_instance
+}
+
+async fn untrack_process(
+    running_instances_map: &Arc<Mutex<RunningInstancesMap>>,
+    slug: &str,
+    running_instance: &RunningInstance,
+    handle: &AppHandle,
+) -> Result<StopMethod, Error> {
+    {
+        let mut process_opt = running_instance.process.lock().await;
+        *process_opt = None;
+    }
+
+    {
+        let mut running_instances = running_instances_map.lock().await;
+        running_instances.remove(slug);
+    }
+    // Only release the subscription this run added, others may still be watching.
+    if running_instance.watching {
+        watcher::unwatch_instance(slug);
+    }
+
+    if let Err(e) = run_registry::remove_record(slug) {
+        warn!("Failed to remove {} from the run registry: {}", slug, e);
+    }
+
+    let stop_method = running_instance
+        .stop_method
+        .lock()
+        .await
+        .unwrap_or(StopMethod::Exited);
+    let message = match stop_method {
+        StopMethod::Exited => "Game instance stopped",
+        StopMethod::Terminated => "Instance stopped by user",
+        StopMethod::Killed => "Instance killed by user",
+    };
+    info!("Instance {} stopped ({:?})", slug, stop_method);
+
+    InstanceStoppedEvent {
+        slug: &slug.replace(".", "_"),
+        message,
+        method: stop_method,
+    }
+    .emit(handle)?;
+
+    Ok(stop_method)
+}
+
+pub async fn reattach_instances(handle: AppHandle) -> Result<(), Error> {
+    let state = handle.state::<AppState>();
+
+    for record in run_registry::get_records()? {
+        if state
+            .running_instances
+            .lock()
+            .await
+            .contains_key(&record.slug)
+        {
+            continue;
+        }
+
+        if record.is_alive() {
+            info!(
+                "Re-attaching to instance {} running with PID {}",
+                record.slug, record.pid
+            );
+
+            let monitor_handle = handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let slug = record.slug.clone();
+                if let Err(e) = monitor_adopted_instance(monitor_handle, record).await {
+                    error!("Error monitoring re-attached instance {}: {:?}", slug, e);
+                }
+            });
+            continue;
+        }
+
+        let ended_at = record.estimate_end_time();
+        let duration_played = (ended_at - record.started_at).num_seconds().max(0) as u64;
+        info!(
+            "Instance {} stopped while the launcher was closed, recording {} seconds played",
+            record.slug, duration_played
+        );
+
+        if let Err(e) =
+            record_play_time(&state, &handle, &record.slug, duration_played, ended_at).await
+        {
+            warn!("Failed to record play time for {}: {}", record.slug, e);
+        }
+
+        run_registry::remove_stale_record(&record)?;
     }
 
     Ok(())
 }
 
+async fn monitor_adopted_instance(handle: AppHandle, record: RunRecord) -> Result<(), Error> {
+    let state = handle.state::<AppState>();
+    let running_instances_map = state.running_instances.clone();
+    let slug = record.slug.clone();
+    let pid = record.pid;
+    let started_at = record.started_at;
+
+    let instance = state
+        .instances
+        .lock()
+        .await
+        .get_instance(&slug)
+        .ok_or_else(|| anyhow!("Instance {} not found", slug))?;
+    let instance_dir = config::get_config_dir()?.join("instances").join(&slug);
+
+    let _launch_guard =
+        LaunchGuard::acquire(&state.launch_states, &handle, &slug, LaunchState::Running)?;
+    let running_instance = track_process(
+        &running_instances_map,
+        &handle,
+        &slug,
+        GameProcess::Adopted(record),
+    )
+    .await;
+
+    let stats_sampler = stats::spawn_sampler(
+        handle.clone(),
+        slug.clone(),
+        pid,
+        Arc::clone(&running_instance.stats),
+    );
+
+    InstanceStartedEvent {
+        slug: &slug.replace(".", "_"),
+        message: "Re-attached to running game instance",
+    }
+    .emit(&handle)?;
+
+    // The exit code of a process this launcher did not spawn cannot be known.
+    let status = wait_for_process_completion(&running_instance.process)
+        .await
+        .map(|_| None);
+
+    let finish_result = finish_instance(
+        &handle,
+        &instance,
+        &instance_dir,
+        &running_instances_map,
+        &running_instance,
+        stats_sampler,
+        status,
+    )
+    .await;
+
+    let ended_at = Utc::now();
+    let duration_played = (ended_at - started_at).num_seconds().max(0) as u64;
+    record_play_time(&state, &handle, &slug, duration_played, ended_at).await?;
+
+    finish_result
+}
+
 async fn wait_for_process_completion(process_handle: &ProcessHandle) -> Result<ExitStatus, Error> {
     tokio::task::spawn_blocking({
         let handle = Arc::clone(process_handle);
@@ -520,6 +908,60 @@ fn construct_classpath(
     Ok(classpath_entries.join(separator))
 }
 
+pub async fn stop_instance(
+    state: State<'_, AppState>,
+    handle: AppHandle,
+    slug: &str,
+) -> Result<(), Error> {
+    info!("Attempting to stop instance: {}", slug);
+
+    let grace_period = Duration::from_secs(config::get_config()?.stop_grace_period);
+    let running_instance = get_running_instance(&state, slug).await?;
+
+    let pid = match process_id(&running_instance.process).await {
+        Some(pid) => pid,
+        None => {
+            info!("Instance {} already stopped.", slug);
+            return Ok(());
+        }
+    };
+
+    *running_instance.stop_method.lock().await = Some(StopMethod::Terminated);
+    launch_state::set_state(&state.launch_states, &handle, slug, LaunchState::Stopping);
+
+    info!("Sending termination signal to PID: {}", pid);
+    if let Err(e) = terminate_process(pid) {
+        warn!("Failed to terminate {} gracefully: {}", slug, e);
+    }
+
+    let formatted_slug = slug.replace(".", "_");
+    let start_time = Instant::now();
+
+    while start_time.elapsed() < grace_period {
+        if has_exited(&running_instance.process).await {
+            info!("Process {} terminated gracefully", slug);
+            return Ok(());
+        }
+
+        InstanceStoppingEvent {
+            slug: &formatted_slug,
+            elapsed: start_time.elapsed().as_secs(),
+            grace_period: grace_period.as_secs(),
+        }
+        .emit(&handle)?;
+
+        tokio::time::sleep(STOP_POLL_INTERVAL).await;
+    }
+
+    warn!(
+        "Instance {} did not stop within {} seconds, killing it",
+        slug,
+        grace_period.as_secs()
+    );
+
+    force_kill(&running_instance, slug).await
+}
+
 pub async fn kill_instance(
     state: State<'_, AppState>,
     handle: AppHandle,
@@ -527,17 +969,15 @@ pub async fn kill_instance(
 ) -> Result<(), Error> {
     info!("Attempting to kill instance: {}", slug);
 
-    let process_handle_arc = get_process_handle(&state, slug).await?;
-    let kill_result = perform_kill(&process_handle_arc, slug).await;
-    cleanup_instance(&state, &handle, slug).await?;
-
-    kill_result
+    let running_instance = get_running_instance(&state, slug).await?;
+    launch_state::set_state(&state.launch_states, &handle, slug, LaunchState::Stopping);
+    force_kill(&running_instance, slug).await
 }
 
-async fn get_process_handle(
+async fn get_running_instance(
     state: &State<'_, AppState>,
     slug: &str,
-) -> Result<ProcessHandle, Error> {
+) -> Result<RunningInstance, Error> {
     let running_instances_map = state.running_instances.clone();
     let instances = running_instances_map.lock().await;
 
@@ -547,126 +987,71 @@ async fn get_process_handle(
     })
 }
 
-async fn perform_kill(process_handle: &ProcessHandle, slug: &str) -> Result<(), Error> {
-    let mut handle_guard = process_handle.lock().await;
+async fn process_id(process_handle: &ProcessHandle) -> Option<u32> {
+    let guard = process_handle.lock().await;
+    match guard.as_ref()? {
+        // An adopted PID may have been reused by another process since the game exited,
+        // never signal it without checking that it is still the same process.
+        GameProcess::Adopted(record) if !record.is_alive() => None,
+        process => Some(process.id()),
+    }
+}
 
-    let child = match handle_guard.as_mut() {
-        Some(child) => child,
+async fn has_exited(process_handle: &ProcessHandle) -> bool {
+    let mut guard = process_handle.lock().await;
+
+    match guard.as_mut() {
+        Some(child) => matches!(child.try_wait(), Ok(Some(_))),
+        None => true,
+    }
+}
+
+async fn force_kill(running_instance: &RunningInstance, slug: &str) -> Result<(), Error> {
+    let pid = match process_id(&running_instance.process).await {
+        Some(pid) => pid,
         None => {
             info!("Instance {} already stopped.", slug);
             return Ok(());
         }
     };
 
-    let pid = child.id();
-    info!("Attempting to kill PID: {}", pid);
-
-    if let Err(e) = child.kill() {
-        error!("Failed to kill {} gracefully: {}", slug, e);
-    }
-
-    if wait_for_termination(pid).await {
-        info!("Process {} terminated", slug);
-        *handle_guard = None;
-        Ok(())
-    } else {
-        force_kill_and_cleanup(pid, &mut handle_guard, slug)
-    }
-}
-
-async fn wait_for_termination(pid: u32) -> bool {
-    let result = tokio::time::timeout(
-        Duration::from_secs(3),
-        tokio::task::spawn_blocking(move || {
-            for _ in 0..30 {
-                thread::sleep(Duration::from_millis(100));
-
-                #[cfg(target_os = "windows")]
-                {
-                    if let Ok(output) = Command::new("tasklist")
-                        .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV"])
-                        .output()
-                    {
-                        let output_str = String::from_utf8_lossy(&output.stdout);
-                        if !output_str.contains(&pid.to_string()) {
-                            return true;
-                        }
-                    }
-                }
-
-                #[cfg(not(target_os = "windows"))]
-                {
-                    if unsafe { libc::kill(pid as i32, 0) } != 0 {
-                        return true;
-                    }
-                }
-            }
-            false
-        }),
-    )
-    .await;
-
-    matches!(result, Ok(Ok(true)))
-}
+    *running_instance.stop_method.lock().await = Some(StopMethod::Killed);
 
-fn force_kill_and_cleanup(
-    pid: u32,
-    process_handle_opt: &mut Option<std::process::Child>,
-    slug: &str,
-) -> Result<(), Error> {
     info!("Attempting force kill for PID: {}", pid);
+    if let Err(e) = kill_process(pid) {
+        error!("Failed to force kill PID {}: {}", pid, e);
+    }
 
-    match force_kill_process(pid) {
-        Ok(_) => {
+    let start_time = Instant::now();
+    while start_time.elapsed() < FORCE_KILL_TIMEOUT {
+        if has_exited(&running_instance.process).await {
             info!("Force killed process {} successfully", pid);
-            *process_handle_opt = None;
-            Ok(())
-        }
-        Err(e) => {
-            error!("Failed to force kill PID {}: {}", pid, e);
-            Err(anyhow!(
-                "Failed to kill instance {}: Process did not respond to kill signals",
-                slug
-            ))
+            return Ok(());
         }
-    }
-}
 
-async fn cleanup_instance(
-    state: &State<'_, AppState>,
-    handle: &AppHandle,
-    slug: &str,
-) -> Result<(), Error> {
-    {
-        let mut instances = state.running_instances.lock().await;
-        instances.remove(slug);
+        tokio::time::sleep(Duration::from_millis(100)).await;
     }
 
-    let formatted_slug = slug.replace(".", "_");
-    InstanceStoppedEvent {
-        slug: &formatted_slug,
-        message: "Instance killed by user",
-    }
-    .emit(handle)
-    .map_err(|e| {
-        error!(
-            "Failed to emit InstanceStoppedEvent after killing {}: {}",
-            slug, e
-        );
-        anyhow!("Failed to emit instance stopped event: {}", e)
-    })?;
-
-    info!(
-        "Instance {} removed from running map after kill attempt.",
+    Err(anyhow!(
+        "Failed to kill instance {}: Process did not respond to kill signals",
         slug
-    );
-    Ok(())
+    ))
+}
+
+#[cfg(target_os = "windows")]
+fn terminate_process(pid: u32) -> Result<(), Error> {
+    taskkill(&["/T", "/PID", &pid.to_string()])
 }
 
 #[cfg(target_os = "windows")]
-fn force_kill_process(pid: u32) -> Result<(), Error> {
+fn kill_process(pid: u32) -> Result<(), Error> {
+    taskkill(&["/F", "/T", "/PID", &pid.to_string()])
+}
+
+#[cfg(target_os = "windows")]
+fn taskkill(args: &[&str]) -> Result<(), Error> {
     let output = Command::new("taskkill")
-        .args(["/F", "/PID", &pid.to_string()])
+        .args(args)
         .output()
         .map_err(|e| anyhow!("Failed to execute taskkill: {}", e))?;
 
@@ -679,16 +1064,33 @@ fn force_kill_process(pid: u32) -> Result<(), Error> {
 }
 
 #[cfg(not(target_os = "windows"))]
-fn force_kill_process(pid: u32) -> Result<(), Error> {
-    let output = Command::new("kill")
-        .args(["-9", &pid.to_string()])
-        .output()
-        .map_err(|e| anyhow!("Failed to execute kill -9: {}", e))?;
+fn terminate_process(pid: u32) -> Result<(), Error> {
+    send_signal(pid, libc::SIGTERM)
+}
 
-    if output.status.success() {
-        Ok(())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(anyhow!("kill -9 failed: {}", stderr))
+#[cfg(not(target_os = "windows"))]
+fn kill_process(pid: u32) -> Result<(), Error> {
+    send_signal(pid, libc::SIGKILL)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn send_signal(pid: u32, signal: libc::c_int) -> Result<(), Error> {
+    let pid = pid as libc::pid_t;
+
+    // The game is spawned as the leader of its own process group, so signalling
+    // the negated PID reaches any helper processes the JVM started as well.
+    if unsafe { libc::kill(-pid, signal) } == 0 || unsafe { libc::kill(pid, signal) } == 0 {
+        return Ok(());
+    }
+
+    let err = std::io::Error::last_os_error();
+    match err.raw_os_error() {
+        Some(libc::ESRCH) => Ok(()),
+        _ => Err(anyhow!(
+            "Failed to send signal {} to PID {}: {}",
+            signal,
+            pid,
+            err
+        )),
     }
 }
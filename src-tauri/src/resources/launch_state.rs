@@ -0,0 +1,148 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Error;
+use log::error;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, State};
+use tauri_specta::Event;
+
+use crate::AppState;
+
+pub type LaunchStatesMap = HashMap<String, LaunchState>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum LaunchState {
+    Preparing,
+    Downloading,
+    BackingUp,
+    Starting,
+    Running,
+    Stopping,
+    Finishing,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type, Event)]
+pub struct InstanceStateChangedEvent<'a> {
+    pub slug: &'a str,
+    pub state: Option<LaunchState>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum LaunchError {
+    AlreadyRunning(String),
+    AlreadyPreparing(String),
+    DowngradeRisk(String),
+    Failed(String),
+}
+
+impl fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LaunchError::AlreadyRunning(slug) => write!(f, "Instance {} is already running", slug),
+            LaunchError::AlreadyPreparing(slug) => {
+                write!(f, "Instance {} is already being prepared for launch", slug)
+            }
+            LaunchError::DowngradeRisk(message) | LaunchError::Failed(message) => {
+                write!(f, "{}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LaunchError {}
+
+impl From<Error> for LaunchError {
+    fn from(error: Error) -> Self {
+        match error.downcast_ref::<LaunchError>() {
+            Some(launch_error) => launch_error.clone(),
+            None => LaunchError::Failed(error.to_string()),
+        }
+    }
+}
+
+pub struct LaunchGuard {
+    slug: String,
+    handle: AppHandle,
+    launch_states: Arc<Mutex<LaunchStatesMap>>,
+}
+
+impl LaunchGuard {
+    pub fn acquire(
+        launch_states: &Arc<Mutex<LaunchStatesMap>>,
+        handle: &AppHandle,
+        slug: &str,
+        initial_state: LaunchState,
+    ) -> Result<Self, LaunchError> {
+        {
+            let mut states = launch_states.lock().unwrap();
+            match states.get(slug) {
+                Some(LaunchState::Running | LaunchState::Stopping | LaunchState::Finishing) => {
+                    return Err(LaunchError::AlreadyRunning(slug.to_string()));
+                }
+                Some(_) => return Err(LaunchError::AlreadyPreparing(slug.to_string())),
+                None => {
+                    states.insert(slug.to_string(), initial_state);
+                }
+            }
+        }
+
+        emit_state_changed(handle, slug, Some(initial_state));
+
+        Ok(Self {
+            slug: slug.to_string(),
+            handle: handle.clone(),
+            launch_states: Arc::clone(launch_states),
+        })
+    }
+
+    pub fn set(&self, state: LaunchState) {
+        set_state(&self.launch_states, &self.handle, &self.slug, state);
+    }
+}
+
+impl Drop for LaunchGuard {
+    fn drop(&mut self) {
+        self.launch_states.lock().unwrap().remove(&self.slug);
+        emit_state_changed(&self.handle, &self.slug, None);
+    }
+}
+
+pub fn set_state(
+    launch_states: &Arc<Mutex<LaunchStatesMap>>,
+    handle: &AppHandle,
+    slug: &str,
+    state: LaunchState,
+) {
+    {
+        let mut states = launch_states.lock().unwrap();
+        match states.get_mut(slug) {
+            Some(current) => *current = state,
+            None => return,
+        }
+    }
+
+    emit_state_changed(handle, slug, Some(state));
+}
+
+pub fn get_instance_state(state: State<'_, AppState>, slug: &str) -> Option<LaunchState> {
+    state.launch_states.lock().unwrap().get(slug).copied()
+}
+
+fn emit_state_changed(handle: &AppHandle, slug: &str, state: Option<LaunchState>) {
+    let formatted_slug = slug.replace(".", "_");
+    if let Err(e) = (InstanceStateChangedEvent {
+        slug: &formatted_slug,
+        state,
+    })
+    .emit(handle)
+    {
+        error!("Failed to emit instance state event for {}: {}", slug, e);
+    }
+}
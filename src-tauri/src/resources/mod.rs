@@ -1,7 +1,19 @@
+pub mod archive;
 pub mod assets;
+pub mod backups;
+pub mod data_version;
+pub mod environment;
 pub mod gpu_prefs;
+pub mod hooks;
 pub mod launch;
+pub mod launch_state;
+pub mod quick_play;
+pub mod run_registry;
 pub mod screenshots;
+pub mod server_ping;
+pub mod servers;
+pub mod stats;
 pub mod version;
 pub mod versions;
+pub mod watcher;
 pub mod worlds;
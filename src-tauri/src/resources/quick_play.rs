@@ -0,0 +1,161 @@
+use std::{fs, path::Path};
+
+use anyhow::{Error, Result, anyhow};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{config, resources::version::VersionManifest};
+
+pub const DEFAULT_PORT: u16 = 25565;
+const HISTORY_LENGTH: usize = 20;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum QuickPlayTarget {
+    Singleplayer { world: String },
+    Multiplayer { address: String },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickPlayEntry {
+    pub target: QuickPlayTarget,
+    #[specta(type = String)]
+    pub timestamp: DateTime<Utc>,
+}
+
+pub fn game_args(
+    target: &QuickPlayTarget,
+    version_manifest: &VersionManifest,
+    instance_dir: &Path,
+) -> Result<Vec<String>, Error> {
+    let mut args = Vec::new();
+
+    if version_manifest.has_game_feature(|f| f.has_quick_plays_support) {
+        let log_path = instance_dir.join("quickPlay").join("java").join("log.json");
+        if let Some(parent) = log_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        args.push("--quickPlayPath".to_string());
+        args.push(log_path.to_string_lossy().to_string());
+    }
+
+    match target {
+        QuickPlayTarget::Singleplayer { world } => {
+            if !instance_dir
+                .join("saves")
+                .join(world)
+                .join("level.dat")
+                .exists()
+            {
+                return Err(anyhow!("World '{}' does not exist in this instance", world));
+            }
+
+            if !version_manifest.has_game_feature(|f| f.is_quick_play_singleplayer) {
+                return Err(anyhow!(
+                    "Minecraft {} does not support launching directly into a world",
+                    version_manifest.id
+                ));
+            }
+
+            args.push("--quickPlaySingleplayer".to_string());
+            args.push(world.clone());
+        }
+        QuickPlayTarget::Multiplayer { address } => {
+            let (host, port) = parse_address(address)?;
+            let port = port.unwrap_or(DEFAULT_PORT);
+
+            if version_manifest.has_game_feature(|f| f.is_quick_play_multiplayer) {
+                args.push("--quickPlayMultiplayer".to_string());
+                args.push(format!("{}:{}", host, port));
+            } else {
+                args.push("--server".to_string());
+                args.push(host);
+                args.push("--port".to_string());
+                args.push(port.to_string());
+            }
+        }
+    }
+
+    Ok(args)
+}
+
+pub fn parse_address(address: &str) -> Result<(String, Option<u16>), Error> {
+    let address = address.trim();
+    if address.is_empty() {
+        return Err(anyhow!("Server address cannot be empty"));
+    }
+
+    // Bracketed IPv6 literals may carry a port after the closing bracket.
+    if let Some(rest) = address.strip_prefix('[') {
+        let (host, rest) = rest
+            .split_once(']')
+            .ok_or_else(|| anyhow!("Invalid server address: {}", address))?;
+        let port = match rest.strip_prefix(':') {
+            Some(port) => Some(parse_port(port, address)?),
+            None if rest.is_empty() => None,
+            None => return Err(anyhow!("Invalid server address: {}", address)),
+        };
+        return Ok((format!("[{}]", host), port));
+    }
+
+    match address.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') => {
+            Ok((host.to_string(), Some(parse_port(port, address)?)))
+        }
+        _ => Ok((address.to_string(), None)),
+    }
+}
+
+fn parse_port(port: &str, address: &str) -> Result<u16, Error> {
+    port.parse::<u16>()
+        .map_err(|_| anyhow!("Invalid port in server address: {}", address))
+}
+
+fn get_history_path(slug: &str) -> Result<std::path::PathBuf, Error> {
+    let config_dir = config::get_config_dir()?;
+    Ok(config_dir
+        .join("instances")
+        .join(slug)
+        .join("quickPlay")
+        .join("history.json"))
+}
+
+pub fn get_history(slug: &str) -> Result<Vec<QuickPlayEntry>, Error> {
+    let history_path = get_history_path(slug)?;
+    if !history_path.exists() {
+        return Ok(vec![]);
+    }
+
+    let history_data = fs::read_to_string(&history_path)
+        .map_err(|e| anyhow!("Failed to read quick play history: {}", e))?;
+    let history = serde_json::from_str::<Vec<QuickPlayEntry>>(&history_data)
+        .map_err(|e| anyhow!("Failed to parse quick play history: {}", e))?;
+
+    Ok(history)
+}
+
+pub fn record_history(slug: &str, target: &QuickPlayTarget) -> Result<(), Error> {
+    let history_path = get_history_path(slug)?;
+    if let Some(parent) = history_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut history = get_history(slug).unwrap_or_default();
+    history.retain(|entry| &entry.target != target);
+    history.insert(
+        0,
+        QuickPlayEntry {
+            target: target.clone(),
+            timestamp: Utc::now(),
+        },
+    );
+    history.truncate(HISTORY_LENGTH);
+
+    let history_data = serde_json::to_string_pretty(&history)?;
+    fs::write(history_path, history_data)
+        .map_err(|e| anyhow!("Failed to write quick play history: {}", e))?;
+
+    Ok(())
+}
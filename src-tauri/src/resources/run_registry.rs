@@ -0,0 +1,153 @@
+use std::{fs, path::PathBuf, sync::Mutex};
+
+use anyhow::{Error, Result, anyhow};
+use chrono::{DateTime, Utc};
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+
+static REGISTRY_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RunRecord {
+    pub slug: String,
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+    pub process_start_time: Option<u64>,
+}
+
+impl RunRecord {
+    pub fn new(slug: &str, pid: u32) -> Self {
+        Self {
+            slug: slug.to_string(),
+            pid,
+            started_at: Utc::now(),
+            process_start_time: process_start_time(pid),
+        }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        match (self.process_start_time, process_start_time(self.pid)) {
+            (Some(recorded), Some(current)) => recorded == current,
+            _ => false,
+        }
+    }
+
+    pub fn estimate_end_time(&self) -> DateTime<Utc> {
+        // The game keeps appending to latest.log until it exits, so its modification
+        // time is the best record of when a session ended while the launcher was closed.
+        let latest_log = config::get_config_dir().ok().and_then(|dir| {
+            fs::metadata(
+                dir.join("instances")
+                    .join(&self.slug)
+                    .join("logs")
+                    .join("latest.log"),
+            )
+            .and_then(|metadata| metadata.modified())
+            .ok()
+        });
+
+        match latest_log.map(DateTime::<Utc>::from) {
+            Some(modified) if modified > self.started_at => modified,
+            _ => {
+                warn!(
+                    "Could not determine when instance {} stopped, not counting play time",
+                    self.slug
+                );
+                self.started_at
+            }
+        }
+    }
+}
+
+fn get_registry_path() -> Result<PathBuf, Error> {
+    let config_dir = config::get_config_dir()?;
+    Ok(config_dir.join("running.json"))
+}
+
+fn read_from_file() -> Result<Vec<RunRecord>, Error> {
+    let registry_path = get_registry_path()?;
+    if !registry_path.exists() {
+        return Ok(vec![]);
+    }
+
+    let registry_data = fs::read_to_string(registry_path)
+        .map_err(|e| anyhow!("Failed to read run registry: {}", e))?;
+    let records = serde_json::from_str::<Vec<RunRecord>>(&registry_data)
+        .map_err(|e| anyhow!("Failed to parse run registry: {}", e))?;
+
+    Ok(records)
+}
+
+fn write_to_file(records: &[RunRecord]) -> Result<(), Error> {
+    let registry_path = get_registry_path()?;
+    let registry_data = serde_json::to_string_pretty(records)?;
+    fs::write(registry_path, registry_data)
+        .map_err(|e| anyhow!("Failed to write run registry: {}", e))?;
+
+    Ok(())
+}
+
+pub fn get_records() -> Result<Vec<RunRecord>, Error> {
+    let _guard = REGISTRY_LOCK.lock().unwrap();
+    read_from_file()
+}
+
+pub fn get_record(slug: &str) -> Result<Option<RunRecord>, Error> {
+    let _guard = REGISTRY_LOCK.lock().unwrap();
+    Ok(read_from_file()?.into_iter().find(|r| r.slug == slug))
+}
+
+pub fn add_record(record: RunRecord) -> Result<(), Error> {
+    let _guard = REGISTRY_LOCK.lock().unwrap();
+    let mut records = read_from_file()?;
+    records.retain(|r| r.slug != record.slug);
+    records.push(record);
+    write_to_file(&records)
+}
+
+pub fn remove_record(slug: &str) -> Result<(), Error> {
+    let _guard = REGISTRY_LOCK.lock().unwrap();
+    let mut records = read_from_file()?;
+    records.retain(|r| r.slug != slug);
+    write_to_file(&records)
+}
+
+// Removes `record` only if it was not replaced by a newer launch of the same instance.
+pub fn remove_stale_record(record: &RunRecord) -> Result<(), Error> {
+    let _guard = REGISTRY_LOCK.lock().unwrap();
+    let mut records = read_from_file()?;
+    records.retain(|r| {
+        r.slug != record.slug
+            || r.pid != record.pid
+            || r.process_start_time != record.process_start_time
+    });
+    write_to_file(&records)
+}
+
+#[cfg(target_os = "linux")]
+fn process_start_time(pid: u32) -> Option<u64> {
+    // Field 22 of /proc/<pid>/stat is the start time in clock ticks after boot, which
+    // tells a re-used PID apart from the process that was originally recorded.
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().nth(19)?.parse().ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_start_time(pid: u32) -> Option<u64> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+    // Without /proc, the start time in seconds since the epoch serves the same purpose.
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::nothing(),
+    );
+    system.process(pid).map(|process| process.start_time())
+}
@@ -1,28 +1,102 @@
-use std::{ffi::OsStr, fs, io::Read, path::PathBuf};
+use std::{
+    collections::HashSet,
+    ffi::OsStr,
+    fs,
+    io::Cursor,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-use anyhow::{Context, Error, Result};
+use anyhow::{Context, Error, Result, anyhow};
 use base64::Engine;
-use log::{error, info};
-use notify::{RecursiveMode, Watcher, event::EventKind};
+use chrono::{DateTime, Duration, Local, NaiveDateTime, TimeZone, Utc};
+use image::ImageFormat;
+use log::{error, info, warn};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use specta::Type;
-use std::sync::Mutex;
-use tauri::AppHandle;
+use tauri::{
+    AppHandle, State,
+    http::{
+        Request, Response, StatusCode,
+        header::{ACCESS_CONTROL_ALLOW_ORIGIN, CONTENT_TYPE},
+    },
+    image::Image,
+};
+use tauri_plugin_clipboard_manager::ClipboardExt;
 use tauri_specta::Event;
-use tokio::sync::oneshot::{self, Sender};
+use uuid::Uuid;
 
-use crate::config;
+use crate::{
+    AppState, config,
+    resources::{
+        archive,
+        watcher::{self, FileChange, FileChangeKind, InstanceFolder},
+        worlds::validate_folder_name,
+    },
+};
+
+const THUMBNAIL_WIDTH: u32 = 320;
+const THUMBNAIL_HEIGHT: u32 = 180;
+const TRASH_ENTRY_FILE: &str = "trashed.json";
+const TRASH_RETENTION_DAYS: i64 = 30;
+
+// Instances the screenshots page subscribed to, so repeated calls hold a single
+// subscription on the instance watcher.
+static SCREENSHOT_WATCHERS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
 
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
+#[serde(rename_all = "camelCase")]
 pub struct Screenshot {
+    pub slug: String,
     pub path: PathBuf,
     pub name: String,
-    pub data: String,
+    pub url: String,
+    pub size: u64,
+    pub width: u32,
+    pub height: u32,
+    #[specta(type = String)]
+    pub created_at: DateTime<Utc>,
+    #[specta(type = String)]
+    pub captured_at: DateTime<Utc>,
+    pub thumbnail: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct ScreenshotPage {
+    pub screenshots: Vec<Screenshot>,
+    pub total: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedScreenshot {
+    pub id: String,
+    pub slug: String,
+    pub name: String,
+    #[specta(type = String)]
+    pub deleted_at: DateTime<Utc>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Type, Event)]
-pub struct ScreenshotEvent(String);
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotEvent {
+    pub slug: String,
+    pub kind: FileChangeKind,
+    pub name: String,
+    pub previous_name: Option<String>,
+}
+
+struct ScreenshotFile {
+    slug: String,
+    path: PathBuf,
+    size: u64,
+    created_at: DateTime<Utc>,
+    captured_at: DateTime<Utc>,
+    modified: SystemTime,
+}
 
 fn get_screenshots_dir(slug: String) -> Result<PathBuf, Error> {
     let config_dir = config::get_config_dir()?;
@@ -46,9 +120,149 @@ pub fn open_screenshots_dir(slug: String) -> Result<(), Error> {
     Ok(())
 }
 
-pub fn get_screenshots(slug: String) -> Result<Vec<Screenshot>, Error> {
-    let screenshots_dir = get_screenshots_dir(slug)?;
-    let mut screenshots = Vec::new();
+fn has_png_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(OsStr::to_str)
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+}
+
+fn is_png(path: &Path) -> bool {
+    path.is_file() && has_png_extension(path)
+}
+
+pub fn watch_screenshots(handle: AppHandle, slug: &str) -> Result<(), Error> {
+    let mut watchers = SCREENSHOT_WATCHERS.lock().unwrap();
+    if watchers.contains(slug) {
+        return Ok(());
+    }
+
+    watcher::watch_instance(handle, slug)?;
+    watchers.insert(slug.to_string());
+    Ok(())
+}
+
+pub fn stop_watching_screenshots(slug: Option<&str>) {
+    let mut watchers = SCREENSHOT_WATCHERS.lock().unwrap();
+    let stopped = match slug {
+        Some(slug) => watchers.take(slug).into_iter().collect::<Vec<_>>(),
+        None => watchers.drain().collect(),
+    };
+
+    for slug in stopped {
+        watcher::unwatch_instance(&slug);
+    }
+}
+
+// The instance watcher reports every folder, the screenshots page only cares about
+// images and keeps its own event.
+pub fn emit_screenshot_changes(handle: &AppHandle, slug: &str, changes: &[FileChange]) {
+    let changes = changes
+        .iter()
+        .filter(|change| change.folder == InstanceFolder::Screenshots);
+
+    for change in changes {
+        let is_png = has_png_extension(Path::new(&change.name));
+        let previous_png = change
+            .previous_name
+            .as_deref()
+            .filter(|name| has_png_extension(Path::new(name)));
+
+        let (kind, name, previous_name) = match (change.kind, previous_png) {
+            (FileChangeKind::Renamed, Some(previous)) if is_png => (
+                FileChangeKind::Renamed,
+                change.name.clone(),
+                Some(previous.to_string()),
+            ),
+            (FileChangeKind::Renamed, Some(previous)) => {
+                (FileChangeKind::Removed, previous.to_string(), None)
+            }
+            (FileChangeKind::Renamed, None) if is_png => {
+                (FileChangeKind::Created, change.name.clone(), None)
+            }
+            (kind, _) if is_png => (kind, change.name.clone(), None),
+            _ => continue,
+        };
+
+        if let Err(e) = (ScreenshotEvent {
+            slug: slug.to_string(),
+            kind,
+            name,
+            previous_name,
+        })
+        .emit(handle)
+        {
+            error!("Failed to emit ScreenshotEvent: {}", e);
+        }
+    }
+}
+
+pub async fn get_screenshots(
+    slug: String,
+    offset: usize,
+    limit: usize,
+) -> Result<ScreenshotPage, Error> {
+    // Decoding and downscaling images is CPU bound, keep it off the async workers.
+    tokio::task::spawn_blocking(move || {
+        let files = read_screenshot_files(&slug)?;
+        build_page(files, offset, limit)
+    })
+    .await?
+}
+
+pub async fn get_screenshot_gallery(
+    state: &State<'_, AppState>,
+    offset: usize,
+    limit: usize,
+) -> Result<ScreenshotPage, Error> {
+    let slugs = {
+        let instances_config = state.instances.lock().await;
+        instances_config
+            .get_instances()
+            .iter()
+            .map(|instance| instance.slug.clone())
+            .collect::<Vec<_>>()
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let mut files = Vec::new();
+        let mut complete = true;
+        for slug in slugs {
+            match read_screenshot_files(&slug) {
+                Ok(instance_files) => files.extend(instance_files),
+                Err(e) => {
+                    warn!("Failed to read screenshots of {}: {}", slug, e);
+                    complete = false;
+                }
+            }
+        }
+
+        // Only the gallery sees every screenshot, so it is the one place that can
+        // tell which cached thumbnails are no longer needed.
+        if complete {
+            if let Err(e) = prune_thumbnails(&files) {
+                warn!("Failed to prune screenshot thumbnails: {}", e);
+            }
+        }
+
+        build_page(files, offset, limit)
+    })
+    .await?
+}
+
+// Minecraft names screenshots after the local time they were taken, which survives
+// copying between machines unlike the file timestamps.
+fn parse_capture_time(name: &str) -> Option<DateTime<Utc>> {
+    let timestamp = name.get(..19)?;
+    let naive = NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d_%H.%M.%S").ok()?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+}
+
+fn read_screenshot_files(slug: &str) -> Result<Vec<ScreenshotFile>, Error> {
+    let screenshots_dir = get_screenshots_dir(slug.to_string())?;
+    let mut files = Vec::new();
 
     for entry_result in fs::read_dir(&screenshots_dir).with_context(|| {
         format!(
@@ -58,118 +272,541 @@ pub fn get_screenshots(slug: String) -> Result<Vec<Screenshot>, Error> {
     })? {
         let entry = entry_result.with_context(|| "Failed to read directory entry")?;
         let path = entry.path();
+        if !is_png(&path) {
+            continue;
+        }
 
-        if path.is_file() {
-            match path.extension().and_then(OsStr::to_str) {
-                Some(ext) if ext.eq_ignore_ascii_case("png") => {
-                    let name = path
-                        .file_name()
-                        .ok_or_else(|| {
-                            Error::msg(format!("Could not get file name from path {:?}", path))
-                        })?
-                        .to_string_lossy()
-                        .to_string();
-
-                    let mut file_content = Vec::new();
-                    fs::File::open(&path)
-                        .with_context(|| format!("Failed to open screenshot file: {:?}", path))?
-                        .read_to_end(&mut file_content)
-                        .with_context(|| format!("Failed to read screenshot file: {:?}", path))?;
-
-                    let encoded_data =
-                        base64::engine::general_purpose::STANDARD.encode(&file_content);
-                    let data = format!("data:image/png;base64,{}", encoded_data);
-
-                    screenshots.push(Screenshot {
-                        path: path.clone(),
-                        name,
-                        data,
-                    });
-                }
-                _ => {
-                    continue;
+        let metadata = entry
+            .metadata()
+            .with_context(|| format!("Failed to read metadata for {:?}", path))?;
+        let modified = metadata.modified()?;
+        let created_at = DateTime::<Utc>::from(metadata.created().unwrap_or(modified));
+        let captured_at =
+            parse_capture_time(&entry.file_name().to_string_lossy()).unwrap_or(created_at);
+
+        files.push(ScreenshotFile {
+            slug: slug.to_string(),
+            path,
+            size: metadata.len(),
+            created_at,
+            captured_at,
+            modified,
+        });
+    }
+
+    Ok(files)
+}
+
+fn build_page(
+    mut files: Vec<ScreenshotFile>,
+    offset: usize,
+    limit: usize,
+) -> Result<ScreenshotPage, Error> {
+    files.sort_by(|a, b| {
+        b.captured_at
+            .cmp(&a.captured_at)
+            .then_with(|| b.path.cmp(&a.path))
+    });
+    let total = files.len();
+
+    let screenshots = files
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .map(|file| {
+            let name = file
+                .path
+                .file_name()
+                .ok_or_else(|| {
+                    Error::msg(format!("Could not get file name from path {:?}", file.path))
+                })?
+                .to_string_lossy()
+                .to_string();
+
+            let (width, height) = image::image_dimensions(&file.path).unwrap_or_else(|e| {
+                warn!("Failed to read dimensions of {:?}: {}", file.path, e);
+                (0, 0)
+            });
+            let thumbnail = match get_thumbnail(&file.path, file.modified, file.size) {
+                Ok(thumbnail) => Some(thumbnail),
+                Err(e) => {
+                    warn!("Failed to create thumbnail for {:?}: {}", file.path, e);
+                    None
                 }
+            };
+
+            Ok(Screenshot {
+                url: screenshot_url(&file.slug, &name),
+                slug: file.slug,
+                path: file.path,
+                name,
+                size: file.size,
+                width,
+                height,
+                created_at: file.created_at,
+                captured_at: file.captured_at,
+                thumbnail,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(ScreenshotPage { screenshots, total })
+}
+
+fn get_thumbnails_dir() -> Result<PathBuf, Error> {
+    Ok(config::get_config_dir()?.join("cache").join("thumbnails"))
+}
+
+// The cache outlives launcher updates, so the key must not depend on the standard
+// library's hasher, which may change between Rust releases.
+fn thumbnail_file_name(path: &Path, modified: SystemTime, size: u64) -> String {
+    let modified = modified.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let mut hasher = Sha256::new();
+    hasher.update(path.as_os_str().as_encoded_bytes());
+    hasher.update(modified.as_nanos().to_le_bytes());
+    hasher.update(size.to_le_bytes());
+
+    let hash = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    format!("{}.jpg", hash)
+}
+
+fn remove_thumbnail(path: &Path) {
+    let Ok(metadata) = fs::metadata(path) else {
+        return;
+    };
+    let Ok(modified) = metadata.modified() else {
+        return;
+    };
+    if let Ok(thumbnails_dir) = get_thumbnails_dir() {
+        let _ = fs::remove_file(thumbnails_dir.join(thumbnail_file_name(
+            path,
+            modified,
+            metadata.len(),
+        )));
+    }
+}
+
+// Screenshots deleted or edited outside the launcher leave their thumbnails behind.
+fn prune_thumbnails(files: &[ScreenshotFile]) -> Result<(), Error> {
+    let thumbnails_dir = get_thumbnails_dir()?;
+    if !thumbnails_dir.exists() {
+        return Ok(());
+    }
+
+    let expected = files
+        .iter()
+        .map(|file| thumbnail_file_name(&file.path, file.modified, file.size))
+        .collect::<HashSet<_>>();
+
+    let mut removed = 0;
+    for entry in fs::read_dir(&thumbnails_dir)? {
+        let entry = entry?;
+        if expected.contains(entry.file_name().to_string_lossy().as_ref()) {
+            continue;
+        }
+        if fs::remove_file(entry.path()).is_ok() {
+            removed += 1;
+        }
+    }
+
+    if removed > 0 {
+        info!("Pruned {} orphaned screenshot thumbnails", removed);
+    }
+    Ok(())
+}
+
+fn get_thumbnail(path: &Path, modified: SystemTime, size: u64) -> Result<String, Error> {
+    let cache_path = get_thumbnails_dir()?.join(thumbnail_file_name(path, modified, size));
+
+    let data = if cache_path.exists() {
+        fs::read(&cache_path)?
+    } else {
+        if let Some(cache_dir) = cache_path.parent() {
+            fs::create_dir_all(cache_dir)?;
+        }
+        let thumbnail = image::open(path)?
+            .thumbnail(THUMBNAIL_WIDTH, THUMBNAIL_HEIGHT)
+            .into_rgb8();
+
+        let mut data = Vec::new();
+        thumbnail.write_to(&mut Cursor::new(&mut data), ImageFormat::Jpeg)?;
+        fs::write(&cache_path, &data)?;
+        data
+    };
+
+    let encoded_data = base64::engine::general_purpose::STANDARD.encode(&data);
+    Ok(format!("data:image/jpeg;base64,{}", encoded_data))
+}
+
+fn screenshot_url(slug: &str, name: &str) -> String {
+    #[cfg(target_os = "windows")]
+    let base = "http://screenshot.localhost";
+    #[cfg(not(target_os = "windows"))]
+    let base = "screenshot://localhost";
+
+    format!("{}/{}/{}", base, percent_encode(slug), percent_encode(name))
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
             }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = value.get(index + 1..index + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
         }
     }
 
-    screenshots.sort_by(|a, b| b.path.cmp(&a.path));
+    String::from_utf8(decoded).ok()
+}
+
+// Serves `screenshot://localhost/<slug>/<file name>` so full size images are only
+// loaded when the UI actually displays them.
+pub fn serve_screenshot(request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let error_response = |status: StatusCode| {
+        Response::builder()
+            .status(status)
+            .body(Vec::new())
+            .unwrap_or_default()
+    };
+
+    let mut segments = request
+        .uri()
+        .path()
+        .trim_start_matches('/')
+        .split('/')
+        .map(percent_decode);
+    let (Some(Some(slug)), Some(Some(name)), None) =
+        (segments.next(), segments.next(), segments.next())
+    else {
+        return error_response(StatusCode::BAD_REQUEST);
+    };
+
+    if validate_folder_name(&slug).is_err() || validate_folder_name(&name).is_err() {
+        return error_response(StatusCode::BAD_REQUEST);
+    }
 
-    Ok(screenshots)
+    let path = match get_screenshots_dir(slug) {
+        Ok(screenshots_dir) => screenshots_dir.join(name),
+        Err(e) => {
+            error!("Failed to resolve screenshots directory: {}", e);
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    if !is_png(&path) {
+        return error_response(StatusCode::NOT_FOUND);
+    }
+
+    match fs::read(&path) {
+        Ok(data) => Response::builder()
+            .header(CONTENT_TYPE, "image/png")
+            .header(ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+            .body(data)
+            .unwrap_or_default(),
+        Err(e) => {
+            error!("Failed to read screenshot {:?}: {}", path, e);
+            error_response(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
 }
 
-static SCREENSHOT_WATCHER: Lazy<Mutex<Option<Sender<()>>>> = Lazy::new(|| Mutex::new(None));
+fn get_screenshot_path(slug: &str, name: &str) -> Result<PathBuf, Error> {
+    validate_folder_name(name)?;
 
-pub fn watch_screenshots(handle: AppHandle, slug: &str) -> Result<(), Error> {
-    let screenshots_dir_path = get_screenshots_dir(slug.to_string())?;
-    let slug = slug.to_string();
-
-    if let Some(tx) = SCREENSHOT_WATCHER.lock().unwrap().take() {
-        let _ = tx.send(());
-        info!("Stopping existing screenshot watcher");
-    }
-
-    let (tx, rx) = oneshot::channel::<()>();
-    SCREENSHOT_WATCHER.lock().unwrap().replace(tx);
-
-    tokio::spawn(async move {
-        let handle = handle.clone();
-        let event_handler = move |res: Result<notify::Event, notify::Error>| match res {
-            Ok(event) => {
-                if let EventKind::Create(_) = event.kind {
-                    for path_buf in event.paths {
-                        if path_buf.is_file()
-                            && path_buf
-                                .extension()
-                                .and_then(OsStr::to_str)
-                                .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
-                        {
-                            if let Err(e) =
-                                ScreenshotEvent("New screenshot added".into()).emit(&handle)
-                            {
-                                error!("Failed to emit ScreenshotEvent: {}", e);
-                            }
-                        }
+    let path = get_screenshots_dir(slug.to_string())?.join(name);
+    if !is_png(&path) {
+        return Err(anyhow!("Screenshot {} does not exist in {}", name, slug));
+    }
+
+    Ok(path)
+}
+
+fn unique_file_name(dir: &Path, name: &str) -> String {
+    let path = Path::new(name);
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| name.to_string());
+    let extension = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+
+    let mut file_name = name.to_string();
+    let mut attempt = 2;
+    while dir.join(&file_name).exists() {
+        file_name = format!("{} ({}){}", stem, attempt, extension);
+        attempt += 1;
+    }
+
+    file_name
+}
+
+fn get_trash_dir() -> Result<PathBuf, Error> {
+    Ok(config::get_config_dir()?.join("trash").join("screenshots"))
+}
+
+pub fn delete_screenshots(slug: &str, names: &[String]) -> Result<Vec<TrashedScreenshot>, Error> {
+    let trash_dir = get_trash_dir()?;
+    if let Err(e) = purge_trash(&trash_dir) {
+        warn!("Failed to purge screenshot trash: {}", e);
+    }
+
+    fs::create_dir_all(&trash_dir)?;
+
+    let mut trashed = Vec::new();
+    for name in names {
+        match trash_screenshot(&trash_dir, slug, name) {
+            Ok(entry) => trashed.push(entry),
+            Err(e) => {
+                // Put back what was already moved, so a failed batch leaves the folder
+                // as it was instead of with part of the selection gone.
+                for entry in trashed.iter().rev() {
+                    if let Err(e) = restore_screenshot(&entry.id) {
+                        error!(
+                            "Failed to restore {} after a failed delete: {}",
+                            entry.name, e
+                        );
                     }
                 }
+                return Err(e);
             }
-            Err(e) => error!("Screenshot watch error: {:?}", e),
-        };
+        }
+    }
+
+    Ok(trashed)
+}
+
+fn trash_screenshot(trash_dir: &Path, slug: &str, name: &str) -> Result<TrashedScreenshot, Error> {
+    let path = get_screenshot_path(slug, name)?;
+    let entry = TrashedScreenshot {
+        id: Uuid::new_v4().to_string(),
+        slug: slug.to_string(),
+        name: name.to_string(),
+        deleted_at: Utc::now(),
+    };
+
+    // Unlike create_dir_all, this fails instead of mixing two screenshots in one entry.
+    let entry_dir = trash_dir.join(&entry.id);
+    fs::create_dir(&entry_dir)?;
+
+    let result = fs::write(
+        entry_dir.join(TRASH_ENTRY_FILE),
+        serde_json::to_string_pretty(&entry)?,
+    )
+    .and_then(|_| {
+        remove_thumbnail(&path);
+        fs::rename(&path, entry_dir.join(name))
+    });
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&entry_dir);
+        return Err(Error::from(e).context(format!("Failed to move {} to the trash", name)));
+    }
+
+    info!("Moved screenshot {} of {} to the trash", name, slug);
+    Ok(entry)
+}
 
-        let mut watcher = match notify::recommended_watcher(event_handler) {
-            Ok(w) => w,
+fn read_trash_entry(entry_dir: &Path) -> Result<TrashedScreenshot, Error> {
+    let data = fs::read_to_string(entry_dir.join(TRASH_ENTRY_FILE))?;
+    Ok(serde_json::from_str(&data)?)
+}
+
+pub fn get_trashed_screenshots() -> Result<Vec<TrashedScreenshot>, Error> {
+    let trash_dir = get_trash_dir()?;
+    if !trash_dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut trashed = fs::read_dir(&trash_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| match read_trash_entry(&entry.path()) {
+            Ok(trashed) => Some(trashed),
             Err(e) => {
-                error!(
-                    "Failed to create screenshot watcher for slug {}: {}",
-                    slug, e
-                );
-                return;
+                warn!("Skipping invalid trash entry {:?}: {}", entry.path(), e);
+                None
             }
-        };
+        })
+        .collect::<Vec<_>>();
+
+    trashed.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    Ok(trashed)
+}
+
+pub fn restore_screenshot(id: &str) -> Result<String, Error> {
+    validate_folder_name(id)?;
+
+    let entry_dir = get_trash_dir()?.join(id);
+    let entry = read_trash_entry(&entry_dir)
+        .with_context(|| format!("Trashed screenshot {} does not exist", id))?;
+    validate_folder_name(&entry.name)?;
+
+    let screenshots_dir = get_screenshots_dir(entry.slug.clone())?;
+    let name = unique_file_name(&screenshots_dir, &entry.name);
+    fs::rename(entry_dir.join(&entry.name), screenshots_dir.join(&name))
+        .with_context(|| format!("Failed to restore screenshot {}", entry.name))?;
+    fs::remove_dir_all(&entry_dir)?;
+
+    info!("Restored screenshot {} of {}", name, entry.slug);
+    Ok(name)
+}
+
+pub fn empty_screenshot_trash() -> Result<(), Error> {
+    let trash_dir = get_trash_dir()?;
+    if trash_dir.exists() {
+        fs::remove_dir_all(&trash_dir)
+            .with_context(|| format!("Failed to empty trash {:?}", trash_dir))?;
+    }
+
+    Ok(())
+}
 
-        if let Err(e) = watcher.watch(&screenshots_dir_path, RecursiveMode::NonRecursive) {
-            error!(
-                "Failed to watch path {:?} for slug {}: {}",
-                screenshots_dir_path, slug, e
-            );
-            return;
+fn purge_trash(trash_dir: &Path) -> Result<(), Error> {
+    if !trash_dir.exists() {
+        return Ok(());
+    }
+
+    let cutoff = Utc::now() - Duration::days(TRASH_RETENTION_DAYS);
+    for entry in fs::read_dir(trash_dir)?.filter_map(|entry| entry.ok()) {
+        let entry_dir = entry.path();
+        if read_trash_entry(&entry_dir).is_ok_and(|trashed| trashed.deleted_at < cutoff) {
+            fs::remove_dir_all(&entry_dir)?;
         }
+    }
+
+    Ok(())
+}
 
-        info!(
-            "Screenshot watcher started for slug: {} on dir {:?}",
-            slug, screenshots_dir_path
-        );
+pub fn rename_screenshot(slug: &str, name: &str, new_name: &str) -> Result<String, Error> {
+    let path = get_screenshot_path(slug, name)?;
 
-        let _ = rx.await;
-        info!("Screenshot watcher task for slug {} is terminating.", slug);
-        SCREENSHOT_WATCHER.lock().unwrap().take();
-    });
+    let new_name = if Path::new(new_name)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+    {
+        new_name.to_string()
+    } else {
+        format!("{}.png", new_name)
+    };
+    validate_folder_name(&new_name)?;
+
+    let new_path = path.with_file_name(&new_name);
+    if new_path.exists() {
+        return Err(anyhow!("A screenshot named {} already exists", new_name));
+    }
+
+    remove_thumbnail(&path);
+    fs::rename(&path, &new_path)
+        .with_context(|| format!("Failed to rename screenshot {}", name))?;
+
+    Ok(new_name)
+}
+
+pub async fn copy_screenshot_to_clipboard(
+    handle: &AppHandle,
+    slug: &str,
+    name: &str,
+) -> Result<(), Error> {
+    let path = get_screenshot_path(slug, name)?;
+    let image =
+        tokio::task::spawn_blocking(move || Ok::<_, Error>(image::open(&path)?.into_rgba8()))
+            .await??;
+
+    let (width, height) = image.dimensions();
+    handle
+        .clipboard()
+        .write_image(&Image::new_owned(image.into_raw(), width, height))?;
 
     Ok(())
 }
 
-pub fn stop_watching_screenshots() {
-    if let Some(tx) = SCREENSHOT_WATCHER.lock().unwrap().take() {
-        let _ = tx.send(());
+pub async fn export_screenshots(
+    slug: &str,
+    names: &[String],
+    destination: PathBuf,
+) -> Result<PathBuf, Error> {
+    let files = names
+        .iter()
+        .map(|name| Ok((get_screenshot_path(slug, name)?, name.clone())))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let archive_path = if destination.is_dir() {
+        destination.join(format!("{}-screenshots.zip", slug))
+    } else {
+        destination
+    };
+
+    if let Err(e) = archive::zip_files(&files, &archive_path).await {
+        let _ = fs::remove_file(&archive_path);
+        return Err(e.context("Failed to export screenshots"));
+    }
+
+    info!(
+        "Exported {} screenshots of {} to {:?}",
+        files.len(),
+        slug,
+        archive_path
+    );
+    Ok(archive_path)
+}
+
+pub async fn move_screenshots(
+    state: &State<'_, AppState>,
+    from_slug: &str,
+    to_slug: &str,
+    names: &[String],
+) -> Result<Vec<String>, Error> {
+    if from_slug == to_slug {
+        return Err(anyhow!("Screenshots are already in {}", to_slug));
+    }
+    if state.instances.lock().await.get_instance(to_slug).is_none() {
+        return Err(anyhow!("Instance {} not found", to_slug));
     }
+
+    let target_dir = get_screenshots_dir(to_slug.to_string())?;
+    let mut moved = Vec::new();
+    for name in names {
+        let path = get_screenshot_path(from_slug, name)?;
+        let new_name = unique_file_name(&target_dir, name);
+        let new_path = target_dir.join(&new_name);
+
+        remove_thumbnail(&path);
+        // Instances can live on different drives, fall back to copying.
+        if fs::rename(&path, &new_path).is_err() {
+            fs::copy(&path, &new_path)
+                .with_context(|| format!("Failed to move screenshot {}", name))?;
+            fs::remove_file(&path)?;
+        }
+
+        moved.push(new_name);
+    }
+
+    info!(
+        "Moved {} screenshots from {} to {}",
+        moved.len(),
+        from_slug,
+        to_slug
+    );
+    Ok(moved)
 }
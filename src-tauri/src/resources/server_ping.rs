@@ -0,0 +1,525 @@
+use std::{cmp::Reverse, net::IpAddr, time::Duration};
+
+use anyhow::{Error, Result, anyhow};
+use chrono::Utc;
+use hickory_resolver::TokioAsyncResolver;
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use specta::Type;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::TcpStream,
+    time::{Instant, timeout, timeout_at},
+};
+
+use crate::resources::quick_play::{self, DEFAULT_PORT};
+
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_PACKET_LENGTH: usize = 2 * 1024 * 1024;
+// -1 asks the server to report its own version instead of checking ours.
+const STATUS_PROTOCOL_VERSION: i32 = -1;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerStatus {
+    pub motd: String,
+    pub version: String,
+    pub protocol: Option<i32>,
+    pub players_online: i32,
+    pub players_max: i32,
+    pub player_sample: Vec<String>,
+    pub favicon: Option<String>,
+    pub latency: u64,
+    pub legacy: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct StatusResponse {
+    version: Option<StatusVersion>,
+    players: Option<StatusPlayers>,
+    description: Option<Value>,
+    favicon: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StatusVersion {
+    name: String,
+    protocol: i32,
+}
+
+#[derive(Deserialize, Debug)]
+struct StatusPlayers {
+    max: i32,
+    online: i32,
+    #[serde(default)]
+    sample: Vec<StatusPlayer>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StatusPlayer {
+    name: String,
+}
+
+pub async fn ping_server(address: &str) -> Result<ServerStatus, Error> {
+    let (host, port) = quick_play::parse_address(address)?;
+    let host = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+
+    let (host, port) = match port {
+        Some(port) => (host, port),
+        None => match resolve_srv(&host).await {
+            Some(target) => target,
+            None => (host, DEFAULT_PORT),
+        },
+    };
+
+    ping(&host, port).await
+}
+
+pub async fn ping(host: &str, port: u16) -> Result<ServerStatus, Error> {
+    ping_until(host, port, Instant::now() + PING_TIMEOUT).await
+}
+
+// Both attempts share one deadline, so an unreachable server is reported after
+// PING_TIMEOUT rather than after twice that.
+async fn ping_until(host: &str, port: u16, deadline: Instant) -> Result<ServerStatus, Error> {
+    match ping_modern(host, port, deadline).await {
+        Ok(status) => Ok(status),
+        Err(modern_error) => {
+            // Servers older than 1.7 drop the modern handshake, so fall back to the
+            // legacy ping before reporting the server as unreachable.
+            info!(
+                "Status ping to {}:{} failed ({}), trying legacy ping",
+                host, port, modern_error
+            );
+            ping_legacy(host, port, deadline)
+                .await
+                .map_err(|legacy_error| {
+                    anyhow!(
+                        "Failed to ping {}:{}: {} (legacy ping: {})",
+                        host,
+                        port,
+                        modern_error,
+                        legacy_error
+                    )
+                })
+        }
+    }
+}
+
+async fn resolve_srv(host: &str) -> Option<(String, u16)> {
+    if host.parse::<IpAddr>().is_ok() {
+        return None;
+    }
+
+    let resolver = TokioAsyncResolver::tokio_from_system_conf().ok()?;
+    let lookup = timeout(
+        PING_TIMEOUT,
+        resolver.srv_lookup(format!("_minecraft._tcp.{}.", host)),
+    )
+    .await
+    .ok()?
+    .ok()?;
+
+    let record = lookup
+        .iter()
+        .min_by_key(|record| (record.priority(), Reverse(record.weight())))?;
+    let target = record.target().to_utf8().trim_end_matches('.').to_string();
+    info!(
+        "Resolved SRV record for {} to {}:{}",
+        host,
+        target,
+        record.port()
+    );
+
+    Some((target, record.port()))
+}
+
+async fn ping_modern(host: &str, port: u16, deadline: Instant) -> Result<ServerStatus, Error> {
+    let mut stream = with_deadline(deadline, async {
+        Ok::<_, Error>(TcpStream::connect((host, port)).await?)
+    })
+    .await?;
+    stream.set_nodelay(true)?;
+
+    let (response, status_latency) = with_deadline(deadline, async {
+        let mut handshake = Vec::new();
+        write_varint(&mut handshake, 0x00);
+        write_varint(&mut handshake, STATUS_PROTOCOL_VERSION);
+        write_string(&mut handshake, host);
+        handshake.extend_from_slice(&port.to_be_bytes());
+        write_varint(&mut handshake, 1);
+        write_packet(&mut stream, &handshake).await?;
+
+        let sent = Instant::now();
+        write_packet(&mut stream, &[0x00]).await?;
+
+        let packet = read_packet(&mut stream).await?;
+        let mut cursor = packet.as_slice();
+        let packet_id = read_varint(&mut cursor).await?;
+        if packet_id != 0x00 {
+            return Err(anyhow!("Unexpected status packet id {:#04x}", packet_id));
+        }
+        let json = read_string(&mut cursor).await?;
+
+        let response = serde_json::from_str::<StatusResponse>(&json)
+            .map_err(|e| anyhow!("Invalid status response: {}", e))?;
+        Ok((response, sent.elapsed()))
+    })
+    .await?;
+
+    // Some proxies never answer the ping packet, the status round trip is a good
+    // enough estimate in that case.
+    let latency = with_deadline(deadline, measure_latency(&mut stream))
+        .await
+        .unwrap_or(status_latency);
+
+    let mut motd = String::new();
+    if let Some(description) = &response.description {
+        flatten_text(description, &mut motd);
+    }
+    let (players_online, players_max, player_sample) = match response.players {
+        Some(players) => (
+            players.online,
+            players.max,
+            players
+                .sample
+                .into_iter()
+                .map(|player| player.name)
+                .collect(),
+        ),
+        None => (0, 0, vec![]),
+    };
+
+    Ok(ServerStatus {
+        motd: strip_formatting(&motd),
+        version: response
+            .version
+            .as_ref()
+            .map(|version| strip_formatting(&version.name))
+            .unwrap_or_default(),
+        protocol: response.version.as_ref().map(|version| version.protocol),
+        players_online,
+        players_max,
+        player_sample,
+        favicon: response.favicon,
+        latency: latency.as_millis() as u64,
+        legacy: false,
+    })
+}
+
+async fn measure_latency(stream: &mut TcpStream) -> Result<Duration, Error> {
+    let payload = Utc::now().timestamp_millis();
+    let mut ping = Vec::new();
+    write_varint(&mut ping, 0x01);
+    ping.extend_from_slice(&payload.to_be_bytes());
+
+    let sent = Instant::now();
+    write_packet(stream, &ping).await?;
+
+    let packet = read_packet(stream).await?;
+    let mut cursor = packet.as_slice();
+    if read_varint(&mut cursor).await? != 0x01 || cursor.read_i64().await? != payload {
+        return Err(anyhow!("Invalid pong response"));
+    }
+
+    Ok(sent.elapsed())
+}
+
+async fn ping_legacy(host: &str, port: u16, deadline: Instant) -> Result<ServerStatus, Error> {
+    let mut stream = with_deadline(deadline, async {
+        Ok::<_, Error>(TcpStream::connect((host, port)).await?)
+    })
+    .await?;
+
+    let (response, latency) = with_deadline(deadline, async {
+        let sent = Instant::now();
+        stream.write_all(&[0xFE, 0x01]).await?;
+
+        let packet_id = stream.read_u8().await?;
+        if packet_id != 0xFF {
+            return Err(anyhow!("Unexpected legacy packet id {:#04x}", packet_id));
+        }
+
+        let length = stream.read_u16().await? as usize;
+        let mut data = vec![0u8; length * 2];
+        stream.read_exact(&mut data).await?;
+        let latency = sent.elapsed();
+
+        let units = data
+            .chunks_exact(2)
+            .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+            .collect::<Vec<_>>();
+        Ok((String::from_utf16_lossy(&units), latency))
+    })
+    .await?;
+
+    parse_legacy_response(&response, latency)
+}
+
+fn parse_legacy_response(response: &str, latency: Duration) -> Result<ServerStatus, Error> {
+    let invalid = || anyhow!("Invalid legacy ping response");
+
+    // 1.4 to 1.6 servers answer with null separated fields, anything older only
+    // sends the MOTD and player counts separated by section signs.
+    let (motd, version, protocol, online, max) = match response.strip_prefix("§1\0") {
+        Some(rest) => {
+            let fields = rest.split('\0').collect::<Vec<_>>();
+            if fields.len() < 5 {
+                return Err(invalid());
+            }
+            (
+                fields[2],
+                fields[1].to_string(),
+                fields[0].parse::<i32>().ok(),
+                fields[3],
+                fields[4],
+            )
+        }
+        None => {
+            let mut fields = response.rsplitn(3, '§');
+            let max = fields.next().ok_or_else(invalid)?;
+            let online = fields.next().ok_or_else(invalid)?;
+            let motd = fields.next().ok_or_else(invalid)?;
+            (motd, String::new(), None, online, max)
+        }
+    };
+
+    Ok(ServerStatus {
+        motd: strip_formatting(motd),
+        version,
+        protocol,
+        players_online: online.trim().parse().map_err(|_| invalid())?,
+        players_max: max.trim().parse().map_err(|_| invalid())?,
+        player_sample: vec![],
+        favicon: None,
+        latency: latency.as_millis() as u64,
+        legacy: true,
+    })
+}
+
+async fn with_deadline<T>(
+    deadline: Instant,
+    future: impl Future<Output = Result<T, Error>>,
+) -> Result<T, Error> {
+    timeout_at(deadline, future)
+        .await
+        .map_err(|_| anyhow!("Timed out after {} seconds", PING_TIMEOUT.as_secs()))?
+}
+
+fn flatten_text(component: &Value, output: &mut String) {
+    match component {
+        Value::String(text) => output.push_str(text),
+        Value::Array(parts) => {
+            for part in parts {
+                flatten_text(part, output);
+            }
+        }
+        Value::Object(object) => {
+            if let Some(Value::String(text)) = object.get("text") {
+                output.push_str(text);
+            }
+            if let Some(extra) = object.get("extra") {
+                flatten_text(extra, output);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn strip_formatting(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '§' {
+            chars.next();
+        } else {
+            output.push(c);
+        }
+    }
+
+    output
+}
+
+fn write_varint(buffer: &mut Vec<u8>, value: i32) {
+    let mut value = value as u32;
+    loop {
+        if value & !0x7F == 0 {
+            buffer.push(value as u8);
+            return;
+        }
+        buffer.push((value & 0x7F) as u8 | 0x80);
+        value >>= 7;
+    }
+}
+
+fn write_string(buffer: &mut Vec<u8>, value: &str) {
+    write_varint(buffer, value.len() as i32);
+    buffer.extend_from_slice(value.as_bytes());
+}
+
+async fn write_packet<W: AsyncWrite + Unpin>(writer: &mut W, data: &[u8]) -> Result<(), Error> {
+    let mut frame = Vec::with_capacity(data.len() + 5);
+    write_varint(&mut frame, data.len() as i32);
+    frame.extend_from_slice(data);
+    writer.write_all(&frame).await?;
+    writer.flush().await?;
+
+    Ok(())
+}
+
+async fn read_varint<R: AsyncRead + Unpin>(reader: &mut R) -> Result<i32, Error> {
+    let mut value = 0u32;
+    for position in 0..5 {
+        let byte = reader.read_u8().await?;
+        value |= u32::from(byte & 0x7F) << (7 * position);
+        if byte & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+
+    Err(anyhow!("VarInt is longer than 5 bytes"))
+}
+
+async fn read_length<R: AsyncRead + Unpin>(reader: &mut R) -> Result<usize, Error> {
+    let length = read_varint(reader).await?;
+    if length < 0 || length as usize > MAX_PACKET_LENGTH {
+        return Err(anyhow!("Invalid length {}", length));
+    }
+
+    Ok(length as usize)
+}
+
+async fn read_packet<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Vec<u8>, Error> {
+    let length = read_length(reader).await?;
+    let mut packet = vec![0u8; length];
+    reader.read_exact(&mut packet).await?;
+
+    Ok(packet)
+}
+
+async fn read_string<R: AsyncRead + Unpin>(reader: &mut R) -> Result<String, Error> {
+    let length = read_length(reader).await?;
+    let mut data = vec![0u8; length];
+    reader.read_exact(&mut data).await?;
+
+    String::from_utf8(data).map_err(|e| anyhow!("Invalid UTF-8 string: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    async fn listen() -> (TcpListener, u16) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        (listener, port)
+    }
+
+    #[tokio::test]
+    async fn modern_status() {
+        let (listener, port) = listen().await;
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+
+            let handshake = read_packet(&mut stream).await.unwrap();
+            let mut cursor = handshake.as_slice();
+            assert_eq!(read_varint(&mut cursor).await.unwrap(), 0x00);
+            assert_eq!(
+                read_varint(&mut cursor).await.unwrap(),
+                STATUS_PROTOCOL_VERSION
+            );
+            assert_eq!(read_string(&mut cursor).await.unwrap(), "127.0.0.1");
+            assert_eq!(read_packet(&mut stream).await.unwrap(), [0x00]);
+
+            let json = r#"{
+                "version": {"name": "1.21.4", "protocol": 769},
+                "players": {"max": 20, "online": 2, "sample": [{"name": "Alex", "id": "0"}]},
+                "description": {"text": "§aHello", "extra": [{"text": " world"}]}
+            }"#;
+            let mut response = Vec::new();
+            write_varint(&mut response, 0x00);
+            write_string(&mut response, json);
+            write_packet(&mut stream, &response).await.unwrap();
+
+            // Echo the ping packet back as the pong.
+            let ping = read_packet(&mut stream).await.unwrap();
+            write_packet(&mut stream, &ping).await.unwrap();
+        });
+
+        let status = ping("127.0.0.1", port).await.unwrap();
+        server.await.unwrap();
+
+        assert_eq!(status.motd, "Hello world");
+        assert_eq!(status.version, "1.21.4");
+        assert_eq!(status.protocol, Some(769));
+        assert_eq!(status.players_online, 2);
+        assert_eq!(status.players_max, 20);
+        assert_eq!(status.player_sample, ["Alex"]);
+        assert!(!status.legacy);
+    }
+
+    #[tokio::test]
+    async fn legacy_status() {
+        let (listener, port) = listen().await;
+        let server = tokio::spawn(async move {
+            // Old servers do not understand the modern handshake and hang up.
+            let (stream, _) = listener.accept().await.unwrap();
+            drop(stream);
+
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 2];
+            stream.read_exact(&mut request).await.unwrap();
+            assert_eq!(request, [0xFE, 0x01]);
+
+            let units = ["§1", "127", "1.6.4", "§eA Minecraft Server", "3", "20"]
+                .join("\0")
+                .encode_utf16()
+                .collect::<Vec<_>>();
+            let mut response = vec![0xFF];
+            response.extend_from_slice(&(units.len() as u16).to_be_bytes());
+            for unit in units {
+                response.extend_from_slice(&unit.to_be_bytes());
+            }
+            stream.write_all(&response).await.unwrap();
+        });
+
+        let status = ping("127.0.0.1", port).await.unwrap();
+        server.await.unwrap();
+
+        assert_eq!(status.motd, "A Minecraft Server");
+        assert_eq!(status.version, "1.6.4");
+        assert_eq!(status.protocol, Some(127));
+        assert_eq!(status.players_online, 3);
+        assert_eq!(status.players_max, 20);
+        assert!(status.legacy);
+    }
+
+    #[tokio::test]
+    async fn unresponsive_server_times_out_once() {
+        let (listener, port) = listen().await;
+        let server = tokio::spawn(async move {
+            // Accept both attempts but never answer either of them.
+            let mut streams = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                streams.push(stream);
+            }
+        });
+
+        let started = Instant::now();
+        let error = ping_until("127.0.0.1", port, started + Duration::from_millis(300))
+            .await
+            .unwrap_err()
+            .to_string();
+        server.abort();
+
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(error.contains("Timed out"), "{}", error);
+        assert!(error.contains("legacy ping"), "{}", error);
+    }
+}
@@ -0,0 +1,243 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use anyhow::{Context, Error, Result, anyhow};
+use fastnbt::Value;
+use log::info;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::config;
+
+const ICON_PREFIX: &str = "data:image/png;base64,";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Server {
+    pub name: String,
+    pub ip: String,
+    pub icon: Option<String>,
+    pub accept_textures: Option<bool>,
+}
+
+impl Server {
+    fn from_compound(compound: &HashMap<String, Value>) -> Self {
+        let string = |key: &str| match compound.get(key) {
+            Some(Value::String(value)) => Some(value.clone()),
+            _ => None,
+        };
+
+        Server {
+            name: string("name").unwrap_or_default(),
+            ip: string("ip").unwrap_or_default(),
+            icon: string("icon").map(|icon| format!("{}{}", ICON_PREFIX, icon)),
+            accept_textures: match compound.get("acceptTextures") {
+                Some(Value::Byte(value)) => Some(*value != 0),
+                _ => None,
+            },
+        }
+    }
+
+    // Only the tags the launcher knows about are touched, anything else the game
+    // stored for the entry (e.g. `hidden`, `preventsChatReports`) is kept as is.
+    fn write_to(&self, compound: &mut HashMap<String, Value>) -> Result<(), Error> {
+        if self.ip.trim().is_empty() {
+            return Err(anyhow!("Server address cannot be empty"));
+        }
+
+        compound.insert("name".to_string(), Value::String(self.name.clone()));
+        compound.insert("ip".to_string(), Value::String(self.ip.trim().to_string()));
+
+        match &self.icon {
+            Some(icon) => {
+                let icon = icon.strip_prefix(ICON_PREFIX).unwrap_or(icon);
+                compound.insert("icon".to_string(), Value::String(icon.to_string()));
+            }
+            None => {
+                compound.remove("icon");
+            }
+        }
+
+        match self.accept_textures {
+            Some(accept) => {
+                compound.insert("acceptTextures".to_string(), Value::Byte(accept as i8));
+            }
+            None => {
+                compound.remove("acceptTextures");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct ServersDat {
+    path: PathBuf,
+    root: HashMap<String, Value>,
+    servers: Vec<HashMap<String, Value>>,
+}
+
+impl ServersDat {
+    fn load(slug: &str) -> Result<Self, Error> {
+        let config_dir = config::get_config_dir()?;
+        let instance_dir = config_dir.join("instances").join(slug);
+        if !instance_dir.is_dir() {
+            return Err(anyhow!("Instance {} not found", slug));
+        }
+
+        let path = instance_dir.join("servers.dat");
+
+        if !path.exists() {
+            return Ok(ServersDat {
+                path,
+                root: HashMap::new(),
+                servers: Vec::new(),
+            });
+        }
+
+        let contents =
+            fs::read(&path).with_context(|| format!("Failed to read servers.dat at {:?}", path))?;
+        let mut root = match fastnbt::from_bytes::<Value>(&contents)
+            .with_context(|| format!("Failed to parse servers.dat at {:?}", path))?
+        {
+            Value::Compound(root) => root,
+            _ => return Err(anyhow!("servers.dat at {:?} is not an NBT compound", path)),
+        };
+
+        let servers = match root.remove("servers") {
+            Some(Value::List(entries)) => entries
+                .into_iter()
+                .filter_map(|entry| match entry {
+                    Value::Compound(compound) => Some(compound),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Ok(ServersDat {
+            path,
+            root,
+            servers,
+        })
+    }
+
+    fn save(self) -> Result<(), Error> {
+        let ServersDat {
+            path,
+            mut root,
+            servers,
+        } = self;
+
+        root.insert(
+            "servers".to_string(),
+            Value::List(servers.into_iter().map(Value::Compound).collect()),
+        );
+        let contents = fastnbt::to_bytes(&Value::Compound(root))
+            .map_err(|e| anyhow!("Failed to serialize servers.dat: {}", e))?;
+
+        let temp_path = path.with_extension("dat.tmp");
+        fs::write(&temp_path, contents)
+            .with_context(|| format!("Failed to write servers.dat at {:?}", temp_path))?;
+        fs::rename(&temp_path, &path)
+            .with_context(|| format!("Failed to replace servers.dat at {:?}", path))?;
+
+        Ok(())
+    }
+
+    fn check_index(&self, index: usize) -> Result<(), Error> {
+        if index >= self.servers.len() {
+            return Err(anyhow!(
+                "Server index {} is out of range ({} servers)",
+                index,
+                self.servers.len()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+pub fn get_servers(slug: &str) -> Result<Vec<Server>, Error> {
+    let servers_dat = ServersDat::load(slug)?;
+    Ok(servers_dat
+        .servers
+        .iter()
+        .map(Server::from_compound)
+        .collect())
+}
+
+pub fn add_server(slug: &str, server: Server) -> Result<(), Error> {
+    let mut servers_dat = ServersDat::load(slug)?;
+    let mut compound = HashMap::new();
+    server.write_to(&mut compound)?;
+    servers_dat.servers.push(compound);
+    servers_dat.save()?;
+
+    info!("Added server {} to instance {}", server.ip, slug);
+    Ok(())
+}
+
+pub fn update_server(slug: &str, index: usize, server: Server) -> Result<(), Error> {
+    let mut servers_dat = ServersDat::load(slug)?;
+    servers_dat.check_index(index)?;
+    server.write_to(&mut servers_dat.servers[index])?;
+    servers_dat.save()
+}
+
+pub fn remove_server(slug: &str, index: usize) -> Result<(), Error> {
+    let mut servers_dat = ServersDat::load(slug)?;
+    servers_dat.check_index(index)?;
+    servers_dat.servers.remove(index);
+    servers_dat.save()
+}
+
+pub fn move_server(slug: &str, from: usize, to: usize) -> Result<(), Error> {
+    let mut servers_dat = ServersDat::load(slug)?;
+    servers_dat.check_index(from)?;
+    servers_dat.check_index(to)?;
+
+    let server = servers_dat.servers.remove(from);
+    servers_dat.servers.insert(to, server);
+    servers_dat.save()
+}
+
+pub fn copy_servers(from_slug: &str, to_slug: &str) -> Result<usize, Error> {
+    if from_slug == to_slug {
+        return Err(anyhow!("Cannot copy servers from an instance to itself"));
+    }
+
+    let source = ServersDat::load(from_slug)?;
+    let mut target = ServersDat::load(to_slug)?;
+
+    let ip_of = |compound: &HashMap<String, Value>| match compound.get("ip") {
+        Some(Value::String(ip)) => Some(ip.to_lowercase()),
+        _ => None,
+    };
+
+    let mut copied = 0;
+    for compound in source.servers {
+        let Some(ip) = ip_of(&compound) else {
+            continue;
+        };
+        if target
+            .servers
+            .iter()
+            .any(|existing| ip_of(existing).as_deref() == Some(ip.as_str()))
+        {
+            continue;
+        }
+
+        target.servers.push(compound);
+        copied += 1;
+    }
+
+    if copied > 0 {
+        target.save()?;
+    }
+
+    info!(
+        "Copied {} servers from instance {} to {}",
+        copied, from_slug, to_slug
+    );
+    Ok(copied)
+}
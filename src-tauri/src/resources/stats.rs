@@ -0,0 +1,261 @@
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+
+use anyhow::{Error, Result, anyhow};
+use chrono::{DateTime, Utc};
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, State, async_runtime::JoinHandle};
+use tauri_specta::Event;
+use tokio::{sync::Mutex, time::Instant};
+
+use crate::AppState;
+
+pub type StatsHistory = Arc<Mutex<VecDeque<InstanceStats>>>;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+const HISTORY_LENGTH: usize = 300;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceStats {
+    #[specta(type = String)]
+    pub timestamp: DateTime<Utc>,
+    pub cpu_percent: f64,
+    pub rss_bytes: u64,
+    pub threads: u32,
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+    pub read_bytes_per_sec: u64,
+    pub write_bytes_per_sec: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type, Event)]
+pub struct InstanceStatsEvent<'a> {
+    pub slug: &'a str,
+    pub stats: InstanceStats,
+}
+
+struct ProcSample {
+    taken_at: Instant,
+    cpu_ticks: u64,
+    rss_bytes: u64,
+    threads: u32,
+    read_bytes: u64,
+    write_bytes: u64,
+}
+
+pub fn new_history() -> StatsHistory {
+    Arc::new(Mutex::new(VecDeque::with_capacity(HISTORY_LENGTH)))
+}
+
+pub fn spawn_sampler(
+    handle: AppHandle,
+    slug: String,
+    pid: u32,
+    history: StatsHistory,
+) -> Option<JoinHandle<()>> {
+    if !cfg!(target_os = "linux") {
+        info!(
+            "Process statistics are only available on Linux, not sampling {}",
+            slug
+        );
+        return None;
+    }
+
+    Some(tauri::async_runtime::spawn(async move {
+        let formatted_slug = slug.replace(".", "_");
+        let mut previous: Option<ProcSample> = None;
+        let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let sample = match read_sample(pid).await {
+                Ok(sample) => sample,
+                Err(e) => {
+                    info!("Stopped sampling statistics for {}: {}", slug, e);
+                    break;
+                }
+            };
+
+            if let Some(previous) = &previous {
+                let stats = compute_stats(previous, &sample);
+
+                {
+                    let mut history = history.lock().await;
+                    if history.len() == HISTORY_LENGTH {
+                        history.pop_front();
+                    }
+                    history.push_back(stats.clone());
+                }
+
+                if let Err(e) = (InstanceStatsEvent {
+                    slug: &formatted_slug,
+                    stats,
+                })
+                .emit(&handle)
+                {
+                    error!("Failed to emit instance stats event for {}: {}", slug, e);
+                }
+            }
+
+            previous = Some(sample);
+        }
+    }))
+}
+
+pub async fn get_instance_stats(
+    state: State<'_, AppState>,
+    slug: &str,
+) -> Result<Vec<InstanceStats>, Error> {
+    let history = {
+        let running_instances = state.running_instances.lock().await;
+        running_instances
+            .get(slug)
+            .map(|instance| instance.stats.clone())
+            .ok_or_else(|| anyhow!("Instance {} is not currently running.", slug))?
+    };
+
+    let history = history.lock().await;
+    Ok(history.iter().cloned().collect())
+}
+
+fn compute_stats(previous: &ProcSample, current: &ProcSample) -> InstanceStats {
+    let elapsed = current
+        .taken_at
+        .duration_since(previous.taken_at)
+        .as_secs_f64()
+        .max(f64::EPSILON);
+
+    let cpu_seconds =
+        current.cpu_ticks.saturating_sub(previous.cpu_ticks) as f64 / clock_ticks_per_second();
+    let per_second = |now: u64, before: u64| (now.saturating_sub(before) as f64 / elapsed) as u64;
+
+    InstanceStats {
+        timestamp: Utc::now(),
+        cpu_percent: cpu_seconds / elapsed * 100.0,
+        rss_bytes: current.rss_bytes,
+        threads: current.threads,
+        read_bytes: current.read_bytes,
+        write_bytes: current.write_bytes,
+        read_bytes_per_sec: per_second(current.read_bytes, previous.read_bytes),
+        write_bytes_per_sec: per_second(current.write_bytes, previous.write_bytes),
+    }
+}
+
+impl ProcSample {
+    fn add(&mut self, other: &ProcSample) {
+        self.cpu_ticks += other.cpu_ticks;
+        self.rss_bytes += other.rss_bytes;
+        self.threads += other.threads;
+        self.read_bytes += other.read_bytes;
+        self.write_bytes += other.write_bytes;
+    }
+}
+
+// The game is spawned as the leader of its own process group, so the group also
+// covers helper processes started by the JVM or by mod loaders.
+async fn read_sample(pgid: u32) -> Result<ProcSample, Error> {
+    // Sampling stops once the leader has exited, even if helpers are still around.
+    let mut sample = read_process(pgid, &read_stat(pgid).await?).await?;
+
+    let mut entries = tokio::fs::read_dir("/proc").await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        if pid == pgid {
+            continue;
+        }
+
+        // Processes can exit between listing /proc and reading their files.
+        let Ok(stat) = read_stat(pid).await else {
+            continue;
+        };
+        if parse_process_group(&stat).ok() != Some(pgid) {
+            continue;
+        }
+        if let Ok(member) = read_process(pid, &stat).await {
+            sample.add(&member);
+        }
+    }
+
+    Ok(sample)
+}
+
+async fn read_stat(pid: u32) -> Result<String, Error> {
+    Ok(tokio::fs::read_to_string(format!("/proc/{}/stat", pid)).await?)
+}
+
+async fn read_process(pid: u32, stat: &str) -> Result<ProcSample, Error> {
+    let proc_dir = format!("/proc/{}", pid);
+    let taken_at = Instant::now();
+
+    let status = tokio::fs::read_to_string(format!("{}/status", proc_dir)).await?;
+    // `io` is only readable by the process owner; missing counters are reported as zero.
+    let io = tokio::fs::read_to_string(format!("{}/io", proc_dir))
+        .await
+        .unwrap_or_default();
+
+    let cpu_ticks = parse_cpu_ticks(stat)?;
+    let rss_bytes = status_field(&status, "VmRSS:").unwrap_or(0) * 1024;
+    let threads = status_field(&status, "Threads:").unwrap_or(0) as u32;
+    let read_bytes = status_field(&io, "read_bytes:").unwrap_or(0);
+    let write_bytes = status_field(&io, "write_bytes:").unwrap_or(0);
+
+    Ok(ProcSample {
+        taken_at,
+        cpu_ticks,
+        rss_bytes,
+        threads,
+        read_bytes,
+        write_bytes,
+    })
+}
+
+fn stat_field(stat: &str, number: usize) -> Result<u64, Error> {
+    // The command name can contain spaces and parentheses, so fields are counted
+    // from the last closing parenthesis, which ends field 2.
+    let (_, rest) = stat
+        .rsplit_once(')')
+        .ok_or_else(|| anyhow!("Malformed /proc stat line"))?;
+
+    rest.split_whitespace()
+        .nth(number - 3)
+        .ok_or_else(|| anyhow!("Missing field {} in /proc stat line", number))?
+        .parse::<u64>()
+        .map_err(|e| anyhow!("Invalid field {} in /proc stat line: {}", number, e))
+}
+
+fn parse_cpu_ticks(stat: &str) -> Result<u64, Error> {
+    // `utime` and `stime` are fields 14 and 15.
+    Ok(stat_field(stat, 14)? + stat_field(stat, 15)?)
+}
+
+fn parse_process_group(stat: &str) -> Result<u32, Error> {
+    Ok(stat_field(stat, 5)? as u32)
+}
+
+fn status_field(contents: &str, key: &str) -> Option<u64> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix(key))
+        .and_then(|value| value.split_whitespace().next())
+        .and_then(|value| value.parse().ok())
+}
+
+#[cfg(unix)]
+fn clock_ticks_per_second() -> f64 {
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks > 0 { ticks as f64 } else { 100.0 }
+}
+
+#[cfg(not(unix))]
+fn clock_ticks_per_second() -> f64 {
+    100.0
+}
@@ -163,6 +163,18 @@ pub struct VersionManifest {
     pub r#type: String,
 }
 
+impl VersionManifest {
+    pub fn has_game_feature(&self, feature: impl Fn(&Features) -> Option<bool>) -> bool {
+        self.arguments.game.iter().any(|argument| match argument {
+            GameArgument::Object(object) => object
+                .rules
+                .iter()
+                .any(|rule| rule.features.as_ref().and_then(&feature).unwrap_or(false)),
+            GameArgument::String(_) => false,
+        })
+    }
+}
+
 pub async fn get_version_manifest(
     state: &State<'_, AppState>,
     url: &String,
@@ -0,0 +1,528 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Component, Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use anyhow::{Context, Error, Result};
+use log::{error, info};
+use notify::{
+    RecommendedWatcher, RecursiveMode, Watcher,
+    event::{EventKind, ModifyKind, RenameMode},
+};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::AppHandle;
+use tauri_specta::Event;
+use tokio::{
+    sync::{
+        mpsc::{self, UnboundedReceiver},
+        oneshot::{self, Receiver, Sender},
+    },
+    time::Instant,
+};
+
+use crate::{config, resources::screenshots};
+
+// Files are usually written in several chunks, wait for them to settle before
+// reporting a change. Folders that never settle, like a world being played, are
+// still reported every few seconds.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+const MAX_DELAY: Duration = Duration::from_secs(5);
+
+static INSTANCE_WATCHERS: Lazy<Mutex<HashMap<String, InstanceWatcher>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+struct InstanceWatcher {
+    subscribers: usize,
+    stop: Sender<()>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum InstanceFolder {
+    Saves,
+    Mods,
+    ResourcePacks,
+    ShaderPacks,
+    Screenshots,
+    CrashReports,
+    Logs,
+}
+
+impl InstanceFolder {
+    const ALL: [InstanceFolder; 7] = [
+        InstanceFolder::Saves,
+        InstanceFolder::Mods,
+        InstanceFolder::ResourcePacks,
+        InstanceFolder::ShaderPacks,
+        InstanceFolder::Screenshots,
+        InstanceFolder::CrashReports,
+        InstanceFolder::Logs,
+    ];
+
+    fn dir_name(self) -> &'static str {
+        match self {
+            InstanceFolder::Saves => "saves",
+            InstanceFolder::Mods => "mods",
+            InstanceFolder::ResourcePacks => "resourcepacks",
+            InstanceFolder::ShaderPacks => "shaderpacks",
+            InstanceFolder::Screenshots => "screenshots",
+            InstanceFolder::CrashReports => "crash-reports",
+            InstanceFolder::Logs => "logs",
+        }
+    }
+
+    fn from_dir_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|folder| folder.dir_name() == name)
+    }
+
+    // Worlds are folders whose contents matter (level.dat, icon.png), everything
+    // else only needs its top-level entries.
+    fn recursive_mode(self) -> RecursiveMode {
+        match self {
+            InstanceFolder::Saves => RecursiveMode::Recursive,
+            _ => RecursiveMode::NonRecursive,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum FileChangeKind {
+    Created,
+    Modified,
+    Removed,
+    Renamed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChange {
+    pub folder: InstanceFolder,
+    pub kind: FileChangeKind,
+    pub name: String,
+    pub previous_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type, Event)]
+pub struct InstanceFilesChangedEvent<'a> {
+    pub slug: &'a str,
+    pub changes: Vec<FileChange>,
+}
+
+enum PendingChange {
+    Created,
+    Modified,
+    Removed,
+    Renamed(String),
+}
+
+struct Pending {
+    change: PendingChange,
+    first_seen: Instant,
+    last_seen: Instant,
+}
+
+impl Pending {
+    fn deadline(&self) -> Instant {
+        (self.last_seen + DEBOUNCE).min(self.first_seen + MAX_DELAY)
+    }
+}
+
+type EntryKey = (InstanceFolder, String);
+
+// A changed path resolved to the top-level entry of a watched folder, `nested` is set
+// when the change happened somewhere inside that entry.
+struct ResolvedPath {
+    key: EntryKey,
+    nested: bool,
+}
+
+struct PendingChanges {
+    instance_dir: PathBuf,
+    changes: HashMap<EntryKey, Pending>,
+    // The source of an in-progress rename, and whether it was still pending creation.
+    rename_from: Option<(EntryKey, bool)>,
+}
+
+impl PendingChanges {
+    fn new(instance_dir: PathBuf) -> Self {
+        Self {
+            instance_dir,
+            changes: HashMap::new(),
+            rename_from: None,
+        }
+    }
+
+    fn resolve(&self, path: &Path) -> Option<ResolvedPath> {
+        let mut components = path
+            .strip_prefix(&self.instance_dir)
+            .ok()?
+            .components()
+            .map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+                _ => None,
+            });
+
+        let folder = InstanceFolder::from_dir_name(&components.next()??)?;
+        let name = components.next()??;
+        Some(ResolvedPath {
+            key: (folder, name),
+            nested: components.next().is_some(),
+        })
+    }
+
+    fn record(&mut self, key: EntryKey, change: PendingChange) {
+        let now = Instant::now();
+        let (previous, first_seen) = match self.changes.remove(&key) {
+            Some(pending) => (Some(pending.change), pending.first_seen),
+            None => (None, now),
+        };
+
+        let change = match (previous, change) {
+            // Something that appeared and disappeared within the debounce window was
+            // never visible to the UI.
+            (Some(PendingChange::Created), PendingChange::Removed) => return,
+            (Some(PendingChange::Created), PendingChange::Modified | PendingChange::Renamed(_)) => {
+                PendingChange::Created
+            }
+            (Some(PendingChange::Renamed(from)), PendingChange::Modified) => {
+                PendingChange::Renamed(from)
+            }
+            (Some(PendingChange::Removed), PendingChange::Created) => PendingChange::Modified,
+            (_, change) => change,
+        };
+
+        self.changes.insert(
+            key,
+            Pending {
+                change,
+                first_seen,
+                last_seen: now,
+            },
+        );
+    }
+
+    // The watched folder created at `path`, if any. Folders missing when the watcher
+    // started are picked up through the instance directory once they appear.
+    fn created_folder(&self, path: &Path) -> Option<InstanceFolder> {
+        if path.parent() != Some(self.instance_dir.as_path()) || !path.is_dir() {
+            return None;
+        }
+        InstanceFolder::from_dir_name(path.file_name()?.to_str()?)
+    }
+
+    // Entries written to a new folder before it was watched.
+    fn record_folder_contents(&mut self, folder: InstanceFolder, folder_dir: &Path) {
+        let Ok(entries) = fs::read_dir(folder_dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            self.record((folder, name), PendingChange::Created);
+        }
+    }
+
+    fn record_path(&mut self, path: &Path, change: PendingChange) {
+        if let Some(resolved) = self.resolve(path) {
+            let change = if resolved.nested {
+                PendingChange::Modified
+            } else {
+                change
+            };
+            self.record(resolved.key, change);
+        }
+    }
+
+    fn is_pending_creation(&self, key: &EntryKey) -> bool {
+        matches!(
+            self.changes.get(key),
+            Some(Pending {
+                change: PendingChange::Created,
+                ..
+            })
+        )
+    }
+
+    fn record_rename(&mut self, from: EntryKey, to: EntryKey, created: bool) {
+        self.changes.remove(&from);
+        let change = if created {
+            PendingChange::Created
+        } else {
+            PendingChange::Renamed(from.1)
+        };
+        self.record(to, change);
+    }
+
+    fn handle(&mut self, event: notify::Event) {
+        let paths = event.paths;
+        match event.kind {
+            EventKind::Create(_) => {
+                for path in &paths {
+                    self.record_path(path, PendingChange::Created);
+                }
+            }
+            EventKind::Remove(_) => {
+                for path in &paths {
+                    self.record_path(path, PendingChange::Removed);
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if paths.len() == 2 => {
+                self.rename_from = None;
+                match (self.resolve(&paths[0]), self.resolve(&paths[1])) {
+                    (Some(from), Some(to))
+                        if !from.nested && !to.nested && from.key.0 == to.key.0 =>
+                    {
+                        let created = self.is_pending_creation(&from.key);
+                        self.record_rename(from.key, to.key, created);
+                    }
+                    _ => {
+                        self.record_path(&paths[0], PendingChange::Removed);
+                        self.record_path(&paths[1], PendingChange::Created);
+                    }
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                for path in &paths {
+                    self.rename_from =
+                        self.resolve(path)
+                            .filter(|resolved| !resolved.nested)
+                            .map(|resolved| {
+                                let created = self.is_pending_creation(&resolved.key);
+                                (resolved.key, created)
+                            });
+                    self.record_path(path, PendingChange::Removed);
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                for path in &paths {
+                    let Some(to) = self.resolve(path) else {
+                        continue;
+                    };
+                    match self.rename_from.take() {
+                        Some((from, created)) if !to.nested && from.0 == to.key.0 => {
+                            self.record_rename(from, to.key, created)
+                        }
+                        _ => self.record_path(path, PendingChange::Created),
+                    }
+                }
+            }
+            // Backends that cannot tell which side of a rename a path is on.
+            EventKind::Modify(ModifyKind::Name(_)) => {
+                for path in &paths {
+                    let change = if path.exists() {
+                        PendingChange::Created
+                    } else {
+                        PendingChange::Removed
+                    };
+                    self.record_path(path, change);
+                }
+            }
+            EventKind::Modify(_) => {
+                for path in &paths {
+                    self.record_path(path, PendingChange::Modified);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn next_deadline(&self) -> Option<Instant> {
+        self.changes.values().map(Pending::deadline).min()
+    }
+
+    fn take_settled(&mut self) -> Vec<FileChange> {
+        let now = Instant::now();
+        let settled = self
+            .changes
+            .iter()
+            .filter(|(_, pending)| pending.deadline() <= now)
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+
+        let mut changes = settled
+            .into_iter()
+            .filter_map(|key| {
+                let pending = self.changes.remove(&key)?;
+                let (folder, name) = key;
+                let exists = self
+                    .instance_dir
+                    .join(folder.dir_name())
+                    .join(&name)
+                    .exists();
+
+                let (kind, previous_name) = match pending.change {
+                    // The entry may have been removed again before it settled.
+                    PendingChange::Created
+                    | PendingChange::Modified
+                    | PendingChange::Renamed(_)
+                        if !exists =>
+                    {
+                        return None;
+                    }
+                    PendingChange::Created => (FileChangeKind::Created, None),
+                    PendingChange::Modified => (FileChangeKind::Modified, None),
+                    PendingChange::Removed => (FileChangeKind::Removed, None),
+                    PendingChange::Renamed(from) => (FileChangeKind::Renamed, Some(from)),
+                };
+
+                Some(FileChange {
+                    folder,
+                    kind,
+                    name,
+                    previous_name,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        changes.sort_by(|a, b| (a.folder as u8, &a.name).cmp(&(b.folder as u8, &b.name)));
+        changes
+    }
+}
+
+fn get_instance_dir(slug: &str) -> Result<PathBuf, Error> {
+    let config_dir = config::get_config_dir()?;
+    let instance_dir = config_dir.join("instances").join(slug);
+
+    // Some backends report canonical paths, so watch and compare against those.
+    fs::canonicalize(&instance_dir)
+        .with_context(|| format!("Instance directory does not exist: {:?}", instance_dir))
+}
+
+pub fn watch_instance(handle: AppHandle, slug: &str) -> Result<(), Error> {
+    let mut watchers = INSTANCE_WATCHERS.lock().unwrap();
+    if let Some(watcher) = watchers.get_mut(slug) {
+        watcher.subscribers += 1;
+        return Ok(());
+    }
+
+    let instance_dir = get_instance_dir(slug)?;
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |res| {
+        let _ = event_tx.send(res);
+    })
+    .with_context(|| format!("Failed to create file watcher for {}", slug))?;
+
+    // Folders are not created here, the instance directory reports them when the game
+    // or the user does.
+    watcher
+        .watch(&instance_dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {:?}", instance_dir))?;
+    for folder in InstanceFolder::ALL {
+        let folder_dir = instance_dir.join(folder.dir_name());
+        if folder_dir.is_dir() {
+            watch_folder(&mut watcher, folder, &folder_dir)?;
+        }
+    }
+
+    let (stop_tx, stop_rx) = oneshot::channel();
+    watchers.insert(
+        slug.to_string(),
+        InstanceWatcher {
+            subscribers: 1,
+            stop: stop_tx,
+        },
+    );
+
+    info!("Started file watcher for instance {}", slug);
+    tokio::spawn(run_watcher(
+        handle,
+        slug.to_string(),
+        instance_dir,
+        watcher,
+        event_rx,
+        stop_rx,
+    ));
+
+    Ok(())
+}
+
+fn watch_folder(
+    watcher: &mut RecommendedWatcher,
+    folder: InstanceFolder,
+    folder_dir: &Path,
+) -> Result<(), Error> {
+    watcher
+        .watch(folder_dir, folder.recursive_mode())
+        .with_context(|| format!("Failed to watch {:?}", folder_dir))
+}
+
+async fn run_watcher(
+    handle: AppHandle,
+    slug: String,
+    instance_dir: PathBuf,
+    // Dropping the watcher at the end of the task stops the notify backend.
+    mut watcher: RecommendedWatcher,
+    mut event_rx: UnboundedReceiver<notify::Result<notify::Event>>,
+    mut stop_rx: Receiver<()>,
+) {
+    let mut pending = PendingChanges::new(instance_dir);
+
+    loop {
+        let deadline = pending.next_deadline();
+        tokio::select! {
+            _ = &mut stop_rx => break,
+            res = event_rx.recv() => match res {
+                Some(Ok(event)) => {
+                    for path in &event.paths {
+                        let Some(folder) = pending.created_folder(path) else {
+                            continue;
+                        };
+                        match watch_folder(&mut watcher, folder, path) {
+                            Ok(()) => pending.record_folder_contents(folder, path),
+                            Err(e) => error!("{:?} for instance {}", e, slug),
+                        }
+                    }
+                    pending.handle(event);
+                }
+                Some(Err(e)) => error!("File watch error for instance {}: {:?}", slug, e),
+                None => break,
+            },
+            _ = tokio::time::sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                let changes = pending.take_settled();
+                if changes.is_empty() {
+                    continue;
+                }
+
+                screenshots::emit_screenshot_changes(&handle, &slug, &changes);
+
+                if let Err(e) = (InstanceFilesChangedEvent {
+                    slug: &slug.replace(".", "_"),
+                    changes,
+                })
+                .emit(&handle)
+                {
+                    error!("Failed to emit InstanceFilesChangedEvent: {}", e);
+                }
+            }
+        }
+    }
+
+    info!("Stopped file watcher for instance {}", slug);
+}
+
+pub fn unwatch_instance(slug: &str) {
+    let mut watchers = INSTANCE_WATCHERS.lock().unwrap();
+    let Some(watcher) = watchers.get_mut(slug) else {
+        return;
+    };
+
+    watcher.subscribers = watcher.subscribers.saturating_sub(1);
+    if watcher.subscribers == 0 {
+        if let Some(watcher) = watchers.remove(slug) {
+            let _ = watcher.stop.send(());
+        }
+    }
+}
+
+pub fn stop_watching_instance(slug: &str) {
+    if let Some(watcher) = INSTANCE_WATCHERS.lock().unwrap().remove(slug) {
+        let _ = watcher.stop.send(());
+    }
+}
@@ -0,0 +1,696 @@
+pub mod players;
+
+use std::{
+    fs::{self},
+    io::{Cursor, Read, Write},
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{Context, Error, Result, anyhow};
+use base64::Engine;
+use chrono::{DateTime, TimeZone, Utc};
+use fastnbt::{Value, from_reader};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+use walkdir::WalkDir;
+
+use crate::{
+    AppState, config,
+    resources::{archive, data_version, launch_state::LaunchError},
+};
+
+pub const SKIPPED_FILES: &[&str] = &["session.lock"];
+const WORLD_ROOT_SEARCH_DEPTH: usize = 4;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct World {
+    pub path: PathBuf,
+    pub folder_name: String,
+    pub level_name: String,
+    #[specta(type = String)]
+    pub last_played: Option<DateTime<Utc>>,
+    pub icon: Option<String>,
+    pub game_mode: Option<GameMode>,
+    pub hardcore: bool,
+    pub difficulty: Option<Difficulty>,
+    pub cheats_allowed: bool,
+    pub seed: Option<String>,
+    pub data_version: Option<i32>,
+    pub version_name: Option<String>,
+    pub spawn: Option<SpawnPosition>,
+    pub day_time: Option<i64>,
+    pub size: u64,
+    pub downgrade_risk: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum GameMode {
+    Survival,
+    Creative,
+    Adventure,
+    Spectator,
+}
+
+impl GameMode {
+    fn from_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(GameMode::Survival),
+            1 => Some(GameMode::Creative),
+            2 => Some(GameMode::Adventure),
+            3 => Some(GameMode::Spectator),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum Difficulty {
+    Peaceful,
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    fn from_id(id: i8) -> Option<Self> {
+        match id {
+            0 => Some(Difficulty::Peaceful),
+            1 => Some(Difficulty::Easy),
+            2 => Some(Difficulty::Normal),
+            3 => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Type)]
+pub struct SpawnPosition {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct LevelDat {
+    data: LevelData,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct LevelData {
+    level_name: String,
+    last_played: Option<i64>,
+    game_type: Option<i32>,
+    #[serde(rename = "hardcore")]
+    hardcore: Option<i8>,
+    difficulty: Option<i8>,
+    #[serde(rename = "allowCommands")]
+    allow_commands: Option<i8>,
+    world_gen_settings: Option<WorldGenSettings>,
+    random_seed: Option<i64>,
+    data_version: Option<i32>,
+    version: Option<LevelVersion>,
+    spawn_x: Option<i32>,
+    spawn_y: Option<i32>,
+    spawn_z: Option<i32>,
+    day_time: Option<i64>,
+}
+
+#[derive(Deserialize, Debug)]
+struct WorldGenSettings {
+    seed: i64,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct LevelVersion {
+    name: String,
+}
+
+fn get_dir_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+fn get_worlds_dir(slug: String) -> Result<PathBuf, Error> {
+    let config_dir = config::get_config_dir()?;
+    let instance_dir = config_dir.join("instances").join(slug);
+    let worlds_dir = instance_dir.join("saves");
+    if !worlds_dir.exists() {
+        fs::create_dir_all(&worlds_dir)
+            .with_context(|| format!("Failed to create worlds directory at {:?}", worlds_dir))?;
+    }
+
+    Ok(worlds_dir)
+}
+
+pub async fn ensure_not_running(
+    state: &State<'_, AppState>,
+    slug: &str,
+    action: &str,
+) -> Result<(), Error> {
+    let is_running = {
+        let instances = state.running_instances.lock().await;
+        instances.contains_key(slug)
+    };
+
+    if is_running {
+        return Err(anyhow!(
+            "Cannot {}: Instance '{}' is currently running.",
+            action,
+            slug
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn validate_folder_name(name: &str) -> Result<(), Error> {
+    let mut components = Path::new(name).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => Ok(()),
+        _ => Err(anyhow!("Invalid folder name: {}", name)),
+    }
+}
+
+pub fn unique_folder_name(dir: &Path, base: &str) -> String {
+    let mut folder_name = base.to_string();
+    let mut attempt = 2;
+    while dir.join(&folder_name).exists() {
+        folder_name = format!("{} ({})", base, attempt);
+        attempt += 1;
+    }
+
+    folder_name
+}
+
+fn get_world_dir(slug: &str, world_name: &str) -> Result<PathBuf, Error> {
+    validate_folder_name(world_name)?;
+
+    let world_path = get_worlds_dir(slug.to_string())?.join(world_name);
+    if !world_path.is_dir() {
+        return Err(anyhow!("World directory does not exist: {:?}", world_path));
+    }
+
+    Ok(world_path)
+}
+
+// Archives and folders often wrap the world in one or more extra directories, the
+// shallowest `level.dat` marks the actual world root.
+fn find_world_root(dir: &Path) -> Option<PathBuf> {
+    WalkDir::new(dir)
+        .max_depth(WORLD_ROOT_SEARCH_DEPTH)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file() && entry.file_name() == "level.dat")
+        .min_by_key(|entry| entry.depth())
+        .and_then(|entry| entry.path().parent().map(Path::to_path_buf))
+}
+
+fn copy_dir(source: &Path, destination: &Path) -> Result<(), Error> {
+    for entry in WalkDir::new(source) {
+        let entry = entry?;
+        let target = destination.join(entry.path().strip_prefix(source)?);
+
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)
+                .with_context(|| format!("Failed to create directory {:?}", target))?;
+        } else if entry.file_type().is_file()
+            && !SKIPPED_FILES.contains(&entry.file_name().to_string_lossy().as_ref())
+        {
+            fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to copy {:?}", entry.path()))?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn copy_world_dir(source: PathBuf, destination: PathBuf) -> Result<(), Error> {
+    let cleanup_path = destination.clone();
+    let result = tokio::task::spawn_blocking(move || copy_dir(&source, &destination)).await?;
+
+    if result.is_err() {
+        let _ = fs::remove_dir_all(&cleanup_path);
+    }
+
+    result
+}
+
+pub async fn import_world(
+    state: &State<'_, AppState>,
+    slug: &str,
+    source: PathBuf,
+) -> Result<String, Error> {
+    ensure_not_running(state, slug, "import world").await?;
+
+    let worlds_dir = get_worlds_dir(slug.to_string())?;
+
+    if source.is_dir() {
+        let root = find_world_root(&source)
+            .ok_or_else(|| anyhow!("No level.dat found in {:?}", source))?;
+        let name = root
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Imported World".to_string());
+
+        let folder_name = unique_folder_name(&worlds_dir, &name);
+        copy_world_dir(root, worlds_dir.join(&folder_name)).await?;
+
+        info!(
+            "Imported world {:?} into {} as {}",
+            source, slug, folder_name
+        );
+        return Ok(folder_name);
+    }
+
+    if source.extension().and_then(|ext| ext.to_str()) != Some("zip") {
+        return Err(anyhow!(
+            "Worlds can only be imported from a folder or a zip file"
+        ));
+    }
+
+    let staging_dir = archive::get_staging_path("").await?;
+    if let Err(e) = archive::extract_zip(&source, &staging_dir).await {
+        let _ = fs::remove_dir_all(&staging_dir);
+        return Err(e.context(format!("Failed to extract {:?}", source)));
+    }
+
+    let Some(root) = find_world_root(&staging_dir) else {
+        let _ = fs::remove_dir_all(&staging_dir);
+        return Err(anyhow!("No level.dat found in {:?}", source));
+    };
+
+    let name = if root == staging_dir {
+        source.file_stem()
+    } else {
+        root.file_name()
+    }
+    .map(|name| name.to_string_lossy().to_string())
+    .unwrap_or_else(|| "Imported World".to_string());
+
+    let folder_name = unique_folder_name(&worlds_dir, &name);
+    let result = fs::rename(&root, worlds_dir.join(&folder_name))
+        .with_context(|| format!("Failed to move imported world into {:?}", worlds_dir));
+    if staging_dir.exists() {
+        let _ = fs::remove_dir_all(&staging_dir);
+    }
+    result?;
+
+    info!(
+        "Imported world {:?} into {} as {}",
+        source, slug, folder_name
+    );
+    Ok(folder_name)
+}
+
+pub async fn export_world(
+    state: &State<'_, AppState>,
+    slug: &str,
+    world_name: &str,
+    destination: PathBuf,
+) -> Result<PathBuf, Error> {
+    ensure_not_running(state, slug, "export world").await?;
+
+    let world_path = get_world_dir(slug, world_name)?;
+    let archive_path = if destination.is_dir() {
+        destination.join(format!("{}.zip", world_name))
+    } else {
+        destination
+    };
+
+    if let Err(e) =
+        archive::zip_dir(&world_path, &archive_path, Some(world_name), SKIPPED_FILES).await
+    {
+        let _ = fs::remove_file(&archive_path);
+        return Err(e.context(format!("Failed to export world {}", world_name)));
+    }
+
+    info!(
+        "Exported world {} of {} to {:?}",
+        world_name, slug, archive_path
+    );
+    Ok(archive_path)
+}
+
+pub async fn duplicate_world(
+    state: &State<'_, AppState>,
+    slug: &str,
+    world_name: &str,
+    new_folder_name: &str,
+) -> Result<(), Error> {
+    ensure_not_running(state, slug, "duplicate world").await?;
+
+    let world_path = get_world_dir(slug, world_name)?;
+    validate_folder_name(new_folder_name)?;
+
+    let new_world_path = get_worlds_dir(slug.to_string())?.join(new_folder_name);
+    if new_world_path.exists() {
+        return Err(anyhow!("A world named {} already exists", new_folder_name));
+    }
+
+    copy_world_dir(world_path, new_world_path).await?;
+
+    info!(
+        "Duplicated world {} of {} as {}",
+        world_name, slug, new_folder_name
+    );
+    Ok(())
+}
+
+pub async fn copy_world_to_instance(
+    state: &State<'_, AppState>,
+    from_slug: &str,
+    world_name: &str,
+    to_slug: &str,
+) -> Result<String, Error> {
+    if from_slug == to_slug {
+        return Err(anyhow!("Cannot copy a world into the same instance"));
+    }
+
+    ensure_not_running(state, from_slug, "copy world").await?;
+    ensure_not_running(state, to_slug, "copy world").await?;
+
+    let world_path = get_world_dir(from_slug, world_name)?;
+    let target_worlds_dir = get_worlds_dir(to_slug.to_string())?;
+    let folder_name = unique_folder_name(&target_worlds_dir, world_name);
+
+    copy_world_dir(world_path, target_worlds_dir.join(&folder_name)).await?;
+
+    info!(
+        "Copied world {} from {} to {} as {}",
+        world_name, from_slug, to_slug, folder_name
+    );
+    Ok(folder_name)
+}
+
+pub async fn rename_world(
+    state: &State<'_, AppState>,
+    slug: &str,
+    world_name: &str,
+    new_name: &str,
+) -> Result<(), Error> {
+    ensure_not_running(state, slug, "rename world").await?;
+
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err(anyhow!("World name cannot be empty"));
+    }
+
+    let level_dat_path = get_world_dir(slug, world_name)?.join("level.dat");
+    let contents = fs::read(&level_dat_path)
+        .with_context(|| format!("Failed to read level.dat for world {}", world_name))?;
+    let mut decompressed = Vec::new();
+    GzDecoder::new(contents.as_slice())
+        .read_to_end(&mut decompressed)
+        .with_context(|| format!("Failed to decompress level.dat for world {}", world_name))?;
+
+    // Round-trip through an untyped value so every tag we don't know about survives.
+    let mut root = fastnbt::from_bytes::<Value>(&decompressed)
+        .with_context(|| format!("Failed to parse level.dat for world {}", world_name))?;
+    let Value::Compound(root_compound) = &mut root else {
+        return Err(anyhow!(
+            "level.dat for world {} is not a compound",
+            world_name
+        ));
+    };
+    let Some(Value::Compound(data)) = root_compound.get_mut("Data") else {
+        return Err(anyhow!(
+            "level.dat for world {} has no Data tag",
+            world_name
+        ));
+    };
+    data.insert("LevelName".to_string(), Value::String(new_name.to_string()));
+
+    let serialized =
+        fastnbt::to_bytes(&root).map_err(|e| anyhow!("Failed to serialize level.dat: {}", e))?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&serialized)?;
+    let compressed = encoder.finish()?;
+
+    fs::copy(
+        &level_dat_path,
+        level_dat_path.with_file_name("level.dat_old"),
+    )
+    .with_context(|| format!("Failed to back up level.dat for world {}", world_name))?;
+    let temp_path = level_dat_path.with_file_name("level.dat.tmp");
+    fs::write(&temp_path, compressed)?;
+    fs::rename(&temp_path, &level_dat_path)?;
+
+    info!("Renamed world {} of {} to {}", world_name, slug, new_name);
+    Ok(())
+}
+
+pub fn open_worlds_dir(slug: String) -> Result<(), Error> {
+    let worlds_dir = get_worlds_dir(slug)?;
+    if let Err(e) = open::that(worlds_dir) {
+        return Err(Error::msg(format!(
+            "Failed to open worlds directory: {}",
+            e
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn open_world_dir(slug: String, world_name: String) -> Result<(), Error> {
+    let worlds_dir = get_worlds_dir(slug)?;
+    let world_path = worlds_dir.join(world_name);
+    if !world_path.exists() {
+        return Err(Error::msg(format!(
+            "World directory does not exist: {:?}",
+            world_path
+        )));
+    }
+
+    if let Err(e) = open::that(world_path) {
+        return Err(Error::msg(format!("Failed to open world directory: {}", e)));
+    }
+
+    Ok(())
+}
+
+pub async fn delete_world(
+    state: State<'_, AppState>,
+    slug: String,
+    world_name: String,
+) -> Result<(), Error> {
+    ensure_not_running(&state, &slug, "delete world").await?;
+
+    let worlds_dir = get_worlds_dir(slug)?;
+    let world_path = worlds_dir.join(&world_name);
+
+    if !world_path.exists() {
+        return Err(Error::msg(format!(
+            "World directory to delete does not exist: {:?}",
+            world_path
+        )));
+    }
+
+    if !world_path.is_dir() {
+        return Err(Error::msg(format!(
+            "Path to delete is not a directory: {:?}",
+            world_path
+        )));
+    }
+
+    fs::remove_dir_all(&world_path).with_context(|| {
+        format!(
+            "Failed to delete world directory: {:?} for world folder {}",
+            world_path, world_name
+        )
+    })?;
+
+    Ok(())
+}
+
+fn read_level_data(world_path: &Path, folder_name: &str) -> Result<Option<LevelData>, Error> {
+    let level_dat_path = world_path.join("level.dat");
+    if !level_dat_path.exists() {
+        return Ok(None);
+    }
+
+    let mut file = fs::File::open(&level_dat_path)
+        .with_context(|| format!("Failed to open level.dat for world {}", folder_name))?;
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents)
+        .with_context(|| format!("Failed to read level.dat for world {}", folder_name))?;
+
+    let cursor = Cursor::new(contents);
+    let mut decoder = GzDecoder::new(cursor);
+
+    match from_reader::<_, LevelDat>(&mut decoder) {
+        Ok(level_dat) => Ok(Some(level_dat.data)),
+        Err(e) => {
+            warn!("Failed to parse level.dat for world {}: {}", folder_name, e);
+            Ok(None)
+        }
+    }
+}
+
+fn is_downgrade(world_data_version: Option<i32>, instance_data_version: Option<i32>) -> bool {
+    match (world_data_version, instance_data_version) {
+        (Some(world), Some(instance)) => world > instance,
+        _ => false,
+    }
+}
+
+pub async fn check_downgrade(
+    slug: &str,
+    world_name: &str,
+    game_version: &str,
+) -> Result<(), Error> {
+    let world_path = get_world_dir(slug, world_name)?;
+    let Some(level_data) = read_level_data(&world_path, world_name)? else {
+        return Ok(());
+    };
+
+    let instance_data_version = data_version::get_data_version(game_version).await;
+    if is_downgrade(level_data.data_version, instance_data_version) {
+        return Err(LaunchError::DowngradeRisk(format!(
+            "World {} was last played in {}, opening it in {} may corrupt it",
+            level_data.level_name,
+            level_data
+                .version
+                .map(|version| version.name)
+                .unwrap_or_else(|| "a newer version".to_string()),
+            game_version
+        ))
+        .into());
+    }
+
+    Ok(())
+}
+
+pub async fn get_worlds(state: State<'_, AppState>, slug: String) -> Result<Vec<World>, Error> {
+    let game_version = {
+        let instances_config = state.instances.lock().await;
+        instances_config
+            .get_instance(&slug)
+            .map(|instance| instance.game.version)
+    };
+    let instance_data_version = match &game_version {
+        Some(game_version) => data_version::get_data_version(game_version).await,
+        None => None,
+    };
+
+    let worlds_dir = get_worlds_dir(slug)?;
+    // Reading level.dat and walking every world for its size is slow on large saves.
+    let mut worlds =
+        tokio::task::spawn_blocking(move || read_worlds(&worlds_dir, instance_data_version))
+            .await??;
+
+    worlds.sort_by(|a, b| {
+        b.last_played
+            .cmp(&a.last_played)
+            .then_with(|| a.level_name.cmp(&b.level_name))
+    });
+
+    Ok(worlds)
+}
+
+fn read_worlds(worlds_dir: &Path, instance_data_version: Option<i32>) -> Result<Vec<World>, Error> {
+    let mut worlds = Vec::new();
+
+    for entry_result in fs::read_dir(worlds_dir)
+        .with_context(|| format!("Failed to read worlds directory: {:?}", worlds_dir))?
+    {
+        let entry = entry_result.with_context(|| "Failed to read directory entry")?;
+        let path = entry.path();
+
+        if path.is_dir() {
+            let folder_name = path
+                .file_name()
+                .ok_or_else(|| {
+                    Error::msg(format!("Could not get folder name from path {:?}", path))
+                })?
+                .to_string_lossy()
+                .to_string();
+
+            let level_data = read_level_data(&path, &folder_name)?;
+
+            let icon_path = path.join("icon.png");
+            let mut icon_data = None;
+            if icon_path.exists() {
+                let mut icon_file_content = Vec::new();
+                match fs::File::open(&icon_path)
+                    .with_context(|| format!("Failed to open icon.png for world {}", folder_name))
+                {
+                    Ok(mut file) => {
+                        if file.read_to_end(&mut icon_file_content).is_ok() {
+                            let encoded_data = base64::engine::general_purpose::STANDARD
+                                .encode(&icon_file_content);
+                            icon_data = Some(format!("data:image/png;base64,{}", encoded_data));
+                        } else {
+                            warn!("Failed to read icon.png for world {}", folder_name);
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Could not open icon.png for world {}: {}", folder_name, e);
+                    }
+                }
+            }
+
+            let mut world = World {
+                path: path.clone(),
+                level_name: folder_name.clone(),
+                folder_name,
+                last_played: None,
+                icon: icon_data,
+                game_mode: None,
+                hardcore: false,
+                difficulty: None,
+                cheats_allowed: false,
+                seed: None,
+                data_version: None,
+                version_name: None,
+                spawn: None,
+                day_time: None,
+                size: get_dir_size(&path),
+                downgrade_risk: false,
+            };
+
+            if let Some(data) = level_data {
+                world.level_name = data.level_name;
+                world.last_played = data
+                    .last_played
+                    .filter(|timestamp_ms| *timestamp_ms > 0)
+                    .and_then(|timestamp_ms| Utc.timestamp_millis_opt(timestamp_ms).single());
+                world.game_mode = data.game_type.and_then(GameMode::from_id);
+                world.hardcore = data.hardcore.is_some_and(|hardcore| hardcore != 0);
+                world.difficulty = data.difficulty.and_then(Difficulty::from_id);
+                world.cheats_allowed = data.allow_commands.is_some_and(|allowed| allowed != 0);
+                // Seeds are sent as strings since they don't fit in a JS number.
+                world.seed = data
+                    .world_gen_settings
+                    .map(|settings| settings.seed)
+                    .or(data.random_seed)
+                    .map(|seed| seed.to_string());
+                world.data_version = data.data_version;
+                world.version_name = data.version.map(|version| version.name);
+                world.spawn = match (data.spawn_x, data.spawn_y, data.spawn_z) {
+                    (Some(x), Some(y), Some(z)) => Some(SpawnPosition { x, y, z }),
+                    _ => None,
+                };
+                world.day_time = data.day_time;
+                world.downgrade_risk = is_downgrade(data.data_version, instance_data_version);
+            }
+
+            worlds.push(world);
+        }
+    }
+
+    Ok(worlds)
+}
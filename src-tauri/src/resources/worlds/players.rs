@@ -0,0 +1,307 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    path::Path,
+};
+
+use anyhow::{Context, Error, Result, anyhow};
+use fastnbt::from_reader;
+use flate2::read::GzDecoder;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use specta::Type;
+
+use super::{get_world_dir, validate_folder_name};
+use crate::config;
+
+const TICKS_PER_SECOND: u64 = 20;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerSummary {
+    pub uuid: String,
+    pub name: Option<String>,
+    pub play_time: u64,
+    pub deaths: u64,
+    pub distance: f64,
+    pub mob_kills: u64,
+    pub player_kills: u64,
+    pub advancements_completed: u32,
+    pub advancements_in_progress: u32,
+    pub has_inventory: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct InventoryItem {
+    pub slot: i8,
+    pub id: String,
+    pub count: i32,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PlayerDat {
+    #[serde(default)]
+    inventory: Vec<NbtItem>,
+}
+
+#[derive(Deserialize, Debug)]
+struct NbtItem {
+    #[serde(rename = "Slot")]
+    slot: Option<i8>,
+    id: String,
+    // Item stacks store `Count` as a byte before 1.20.5 and `count` as an int after.
+    #[serde(rename = "Count")]
+    legacy_count: Option<i8>,
+    count: Option<i32>,
+}
+
+#[derive(Deserialize, Debug)]
+struct UserCacheEntry {
+    name: String,
+    uuid: String,
+}
+
+#[derive(Default)]
+struct StatsSummary {
+    play_time_ticks: u64,
+    deaths: u64,
+    distance_cm: u64,
+    mob_kills: u64,
+    player_kills: u64,
+}
+
+fn normalize_uuid(uuid: &str) -> String {
+    uuid.replace('-', "").to_lowercase()
+}
+
+fn player_uuids(world_path: &Path) -> BTreeSet<String> {
+    let mut uuids = BTreeSet::new();
+    for (dir, extension) in [
+        ("stats", "json"),
+        ("advancements", "json"),
+        ("playerdata", "dat"),
+    ] {
+        let Ok(entries) = fs::read_dir(world_path.join(dir)) else {
+            continue;
+        };
+
+        for path in entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+        {
+            if path.extension().and_then(|ext| ext.to_str()) != Some(extension) {
+                continue;
+            }
+            if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                uuids.insert(stem.to_string());
+            }
+        }
+    }
+
+    uuids
+}
+
+fn known_player_names(world_path: &Path) -> HashMap<String, String> {
+    let mut names = HashMap::new();
+
+    // The user cache lives in the game directory, two levels above the world.
+    let user_cache_path = world_path
+        .parent()
+        .and_then(Path::parent)
+        .map(|instance_dir| instance_dir.join("usercache.json"));
+    if let Some(user_cache_path) = user_cache_path.filter(|path| path.exists()) {
+        match read_user_cache(&user_cache_path) {
+            Ok(entries) => {
+                for entry in entries {
+                    names.insert(normalize_uuid(&entry.uuid), entry.name);
+                }
+            }
+            Err(e) => warn!("Failed to read usercache.json: {}", e),
+        }
+    }
+
+    match config::get_config() {
+        Ok(config) => {
+            for account in config.accounts {
+                if !account.profile.id.is_empty() {
+                    names.insert(normalize_uuid(&account.profile.id), account.profile.name);
+                }
+            }
+        }
+        Err(e) => warn!("Failed to read accounts for player names: {}", e),
+    }
+
+    names
+}
+
+fn read_user_cache(path: &Path) -> Result<Vec<UserCacheEntry>, Error> {
+    let data = fs::read_to_string(path)?;
+    Ok(serde_json::from_str::<Vec<UserCacheEntry>>(&data)?)
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    let data = fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&data) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            warn!("Failed to parse {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+fn summarize_stats(stats: &Value) -> StatsSummary {
+    if let Some(custom) = stats
+        .pointer("/stats/minecraft:custom")
+        .and_then(Value::as_object)
+    {
+        let get = |key: &str| custom.get(key).and_then(Value::as_u64).unwrap_or(0);
+        return StatsSummary {
+            // Renamed from play_one_minute in 1.17, the value was always in ticks.
+            play_time_ticks: get("minecraft:play_time").max(get("minecraft:play_one_minute")),
+            deaths: get("minecraft:deaths"),
+            distance_cm: custom
+                .iter()
+                .filter(|(key, _)| key.ends_with("_one_cm"))
+                .filter_map(|(_, value)| value.as_u64())
+                .sum(),
+            mob_kills: get("minecraft:mob_kills"),
+            player_kills: get("minecraft:player_kills"),
+        };
+    }
+
+    // Before 1.13 stats were a flat object of `stat.*` keys.
+    let Some(legacy) = stats.as_object() else {
+        return StatsSummary::default();
+    };
+    let get = |key: &str| legacy.get(key).and_then(Value::as_u64).unwrap_or(0);
+    StatsSummary {
+        play_time_ticks: get("stat.playOneMinute"),
+        deaths: get("stat.deaths"),
+        distance_cm: legacy
+            .iter()
+            .filter(|(key, _)| key.starts_with("stat.") && key.ends_with("OneCm"))
+            .filter_map(|(_, value)| value.as_u64())
+            .sum(),
+        mob_kills: get("stat.mobKills"),
+        player_kills: get("stat.playerKills"),
+    }
+}
+
+fn summarize_advancements(advancements: &Value) -> (u32, u32) {
+    let Some(advancements) = advancements.as_object() else {
+        return (0, 0);
+    };
+
+    let mut completed = 0;
+    let mut in_progress = 0;
+    for (id, progress) in advancements {
+        if id == "DataVersion" || id.contains(":recipes/") {
+            continue;
+        }
+
+        if progress
+            .get("done")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+        {
+            completed += 1;
+        } else {
+            in_progress += 1;
+        }
+    }
+
+    (completed, in_progress)
+}
+
+pub async fn get_world_players(
+    slug: String,
+    world_name: String,
+) -> Result<Vec<PlayerSummary>, Error> {
+    tokio::task::spawn_blocking(move || read_world_players(&slug, &world_name)).await?
+}
+
+pub async fn get_player_inventory(
+    slug: String,
+    world_name: String,
+    uuid: String,
+) -> Result<Vec<InventoryItem>, Error> {
+    tokio::task::spawn_blocking(move || read_player_inventory(&slug, &world_name, &uuid)).await?
+}
+
+fn read_world_players(slug: &str, world_name: &str) -> Result<Vec<PlayerSummary>, Error> {
+    let world_path = get_world_dir(slug, world_name)?;
+    let names = known_player_names(&world_path);
+
+    let mut players = player_uuids(&world_path)
+        .into_iter()
+        .map(|uuid| {
+            let stats = read_json(&world_path.join("stats").join(format!("{}.json", uuid)))
+                .map(|stats| summarize_stats(&stats))
+                .unwrap_or_default();
+            let (advancements_completed, advancements_in_progress) = read_json(
+                &world_path
+                    .join("advancements")
+                    .join(format!("{}.json", uuid)),
+            )
+            .map(|advancements| summarize_advancements(&advancements))
+            .unwrap_or((0, 0));
+
+            PlayerSummary {
+                name: names.get(&normalize_uuid(&uuid)).cloned(),
+                play_time: stats.play_time_ticks / TICKS_PER_SECOND,
+                deaths: stats.deaths,
+                distance: stats.distance_cm as f64 / 100.0,
+                mob_kills: stats.mob_kills,
+                player_kills: stats.player_kills,
+                advancements_completed,
+                advancements_in_progress,
+                has_inventory: world_path
+                    .join("playerdata")
+                    .join(format!("{}.dat", uuid))
+                    .exists(),
+                uuid,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    players.sort_by(|a, b| b.play_time.cmp(&a.play_time));
+    Ok(players)
+}
+
+fn read_player_inventory(
+    slug: &str,
+    world_name: &str,
+    uuid: &str,
+) -> Result<Vec<InventoryItem>, Error> {
+    validate_folder_name(uuid)?;
+
+    let player_dat_path = get_world_dir(slug, world_name)?
+        .join("playerdata")
+        .join(format!("{}.dat", uuid));
+    if !player_dat_path.exists() {
+        return Err(anyhow!(
+            "No player data found for {} in {}",
+            uuid,
+            world_name
+        ));
+    }
+
+    let contents = fs::read(&player_dat_path)
+        .with_context(|| format!("Failed to read player data for {}", uuid))?;
+
+    let player_dat = from_reader::<_, PlayerDat>(GzDecoder::new(contents.as_slice()))
+        .with_context(|| format!("Failed to parse player data for {}", uuid))?;
+
+    Ok(player_dat
+        .inventory
+        .into_iter()
+        .map(|item| InventoryItem {
+            slot: item.slot.unwrap_or_default(),
+            count: item.count.or(item.legacy_count.map(i32::from)).unwrap_or(1),
+            id: item.id,
+        })
+        .collect())
+}
@@ -3,7 +3,7 @@
 /** user-defined commands **/
 
 export const commands = {
-	async login(): Promise<Result<Profile, string>> {
+	async login(): Promise<Result<AccountSummary, string>> {
 		try {
 			return { status: "ok", data: await TAURI_INVOKE("login") };
 		} catch (e) {
@@ -11,6 +11,22 @@ export const commands = {
 			else return { status: "error", error: e as any };
 		}
 	},
+	async addOfflineAccount(name: string): Promise<Result<AccountSummary, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("add_offline_account", { name }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async loginYggdrasil(server: string, username: string, password: string): Promise<Result<AccountSummary, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("login_yggdrasil", { server, username, password }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
 	async cancelLogin(): Promise<Result<null, string>> {
 		try {
 			return { status: "ok", data: await TAURI_INVOKE("cancel_login") };
@@ -43,7 +59,7 @@ export const commands = {
 			else return { status: "error", error: e as any };
 		}
 	},
-	async getActiveAccount(): Promise<Result<Account | null, string>> {
+	async getActiveAccount(): Promise<Result<AccountSummary | null, string>> {
 		try {
 			return { status: "ok", data: await TAURI_INVOKE("get_active_account") };
 		} catch (e) {
@@ -51,7 +67,7 @@ export const commands = {
 			else return { status: "error", error: e as any };
 		}
 	},
-	async getMinecraftProfiles(): Promise<Result<Profile[], string>> {
+	async getMinecraftProfiles(): Promise<Result<AccountSummary[], string>> {
 		try {
 			return { status: "ok", data: await TAURI_INVOKE("get_minecraft_profiles") };
 		} catch (e) {
@@ -107,6 +123,14 @@ export const commands = {
 			else return { status: "error", error: e as any };
 		}
 	},
+	async getSystemMemory(): Promise<Result<number | null, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("get_system_memory") };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
 	async getInstances(): Promise<Result<Instance[], string>> {
 		try {
 			return { status: "ok", data: await TAURI_INVOKE("get_instances") };
@@ -147,9 +171,17 @@ export const commands = {
 			else return { status: "error", error: e as any };
 		}
 	},
-	async launchInstance(slug: string): Promise<Result<null, string>> {
+	async launchInstance(slug: string, target: QuickPlayTarget | null, allowDowngrade: boolean | null): Promise<Result<null, LaunchError>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("launch_instance", { slug, target, allowDowngrade }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async stopInstance(slug: string): Promise<Result<null, string>> {
 		try {
-			return { status: "ok", data: await TAURI_INVOKE("launch_instance", { slug }) };
+			return { status: "ok", data: await TAURI_INVOKE("stop_instance", { slug }) };
 		} catch (e) {
 			if (e instanceof Error) throw e;
 			else return { status: "error", error: e as any };
@@ -163,6 +195,30 @@ export const commands = {
 			else return { status: "error", error: e as any };
 		}
 	},
+	async getInstanceState(slug: string): Promise<Result<LaunchState | null, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("get_instance_state", { slug }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async getInstanceStats(slug: string): Promise<Result<InstanceStats[], string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("get_instance_stats", { slug }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async getQuickPlayHistory(slug: string): Promise<Result<QuickPlayEntry[], string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("get_quick_play_history", { slug }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
 	async getVersions(): Promise<Result<Version[], string>> {
 		try {
 			return { status: "ok", data: await TAURI_INVOKE("get_versions") };
@@ -211,9 +267,81 @@ export const commands = {
 			else return { status: "error", error: e as any };
 		}
 	},
-	async getScreenshots(slug: string): Promise<Result<Screenshot[], string>> {
+	async getScreenshots(slug: string, offset: number, limit: number): Promise<Result<ScreenshotPage, string>> {
 		try {
-			return { status: "ok", data: await TAURI_INVOKE("get_screenshots", { slug }) };
+			return { status: "ok", data: await TAURI_INVOKE("get_screenshots", { slug, offset, limit }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async getScreenshotGallery(offset: number, limit: number): Promise<Result<ScreenshotPage, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("get_screenshot_gallery", { offset, limit }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async deleteScreenshots(slug: string, names: string[]): Promise<Result<TrashedScreenshot[], string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("delete_screenshots", { slug, names }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async getTrashedScreenshots(): Promise<Result<TrashedScreenshot[], string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("get_trashed_screenshots") };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async restoreScreenshot(id: string): Promise<Result<string, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("restore_screenshot", { id }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async emptyScreenshotTrash(): Promise<Result<null, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("empty_screenshot_trash") };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async renameScreenshot(slug: string, name: string, newName: string): Promise<Result<string, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("rename_screenshot", { slug, name, newName }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async copyScreenshotToClipboard(slug: string, name: string): Promise<Result<null, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("copy_screenshot_to_clipboard", { slug, name }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async exportScreenshots(slug: string, names: string[], destination: string): Promise<Result<string, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("export_screenshots", { slug, names, destination }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async moveScreenshots(fromSlug: string, toSlug: string, names: string[]): Promise<Result<string[], string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("move_screenshots", { fromSlug, toSlug, names }) };
 		} catch (e) {
 			if (e instanceof Error) throw e;
 			else return { status: "error", error: e as any };
@@ -235,9 +363,25 @@ export const commands = {
 			else return { status: "error", error: e as any };
 		}
 	},
-	async stopWatchingScreenshots(): Promise<Result<null, string>> {
+	async stopWatchingScreenshots(slug: string | null): Promise<Result<null, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("stop_watching_screenshots", { slug }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async watchInstanceFiles(slug: string): Promise<Result<null, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("watch_instance_files", { slug }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async unwatchInstanceFiles(slug: string): Promise<Result<null, string>> {
 		try {
-			return { status: "ok", data: await TAURI_INVOKE("stop_watching_screenshots") };
+			return { status: "ok", data: await TAURI_INVOKE("unwatch_instance_files", { slug }) };
 		} catch (e) {
 			if (e instanceof Error) throw e;
 			else return { status: "error", error: e as any };
@@ -274,19 +418,176 @@ export const commands = {
 			if (e instanceof Error) throw e;
 			else return { status: "error", error: e as any };
 		}
+	},
+	async getWorldPlayers(slug: string, worldName: string): Promise<Result<PlayerSummary[], string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("get_world_players", { slug, worldName }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async getPlayerInventory(slug: string, worldName: string, uuid: string): Promise<Result<InventoryItem[], string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("get_player_inventory", { slug, worldName, uuid }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async importWorld(slug: string, source: string): Promise<Result<string, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("import_world", { slug, source }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async exportWorld(slug: string, worldName: string, destination: string): Promise<Result<string, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("export_world", { slug, worldName, destination }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async duplicateWorld(slug: string, worldName: string, newFolderName: string): Promise<Result<null, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("duplicate_world", { slug, worldName, newFolderName }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async renameWorld(slug: string, worldName: string, newName: string): Promise<Result<null, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("rename_world", { slug, worldName, newName }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async copyWorldToInstance(fromSlug: string, worldName: string, toSlug: string): Promise<Result<string, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("copy_world_to_instance", { fromSlug, worldName, toSlug }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async backupWorld(slug: string, worldName: string): Promise<Result<WorldBackup, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("backup_world", { slug, worldName }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async getWorldBackups(slug: string, worldName: string | null): Promise<Result<WorldBackup[], string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("get_world_backups", { slug, worldName }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async restoreWorldBackup(slug: string, worldName: string, backupId: string, target: RestoreTarget): Promise<Result<string, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("restore_world_backup", { slug, worldName, backupId, target }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async deleteWorldBackup(slug: string, worldName: string, backupId: string): Promise<Result<null, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("delete_world_backup", { slug, worldName, backupId }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async pruneWorldBackups(slug: string, worldName: string): Promise<Result<number, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("prune_world_backups", { slug, worldName }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async getServers(slug: string): Promise<Result<Server[], string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("get_servers", { slug }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async addServer(slug: string, server: Server): Promise<Result<null, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("add_server", { slug, server }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async updateServer(slug: string, index: number, server: Server): Promise<Result<null, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("update_server", { slug, index, server }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async removeServer(slug: string, index: number): Promise<Result<null, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("remove_server", { slug, index }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async moveServer(slug: string, from: number, to: number): Promise<Result<null, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("move_server", { slug, from, to }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async copyServers(fromSlug: string, toSlug: string): Promise<Result<number, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("copy_servers", { fromSlug, toSlug }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
+	},
+	async pingServer(address: string): Promise<Result<ServerStatus, string>> {
+		try {
+			return { status: "ok", data: await TAURI_INVOKE("ping_server", { address }) };
+		} catch (e) {
+			if (e instanceof Error) throw e;
+			else return { status: "error", error: e as any };
+		}
 	}
 };
 
 /** user-defined events **/
 
 export const events = __makeEvents__<{
+	accountNeedsReauthEvent: AccountNeedsReauthEvent;
 	assetProgressEvent: AssetProgressEvent;
 	assetsDownloadFinishedEvent: AssetsDownloadFinishedEvent;
 	assetsDownloadStartedEvent: AssetsDownloadStartedEvent;
+	instanceFilesChangedEvent: InstanceFilesChangedEvent;
 	instanceListUpdatedEvent: InstanceListUpdatedEvent;
 	instanceLogEvent: InstanceLogEvent;
 	instanceStartedEvent: InstanceStartedEvent;
+	instanceStateChangedEvent: InstanceStateChangedEvent;
+	instanceStatsEvent: InstanceStatsEvent;
 	instanceStoppedEvent: InstanceStoppedEvent;
+	instanceStoppingEvent: InstanceStoppingEvent;
 	javaDownloadFinishedEvent: JavaDownloadFinishedEvent;
 	javaDownloadProgressEvent: JavaDownloadProgressEvent;
 	javaDownloadStartedEvent: JavaDownloadStartedEvent;
@@ -296,13 +597,18 @@ export const events = __makeEvents__<{
 	loginDetailsEvent: LoginDetailsEvent;
 	screenshotEvent: ScreenshotEvent;
 }>({
+	accountNeedsReauthEvent: "account-needs-reauth-event",
 	assetProgressEvent: "asset-progress-event",
 	assetsDownloadFinishedEvent: "assets-download-finished-event",
 	assetsDownloadStartedEvent: "assets-download-started-event",
+	instanceFilesChangedEvent: "instance-files-changed-event",
 	instanceListUpdatedEvent: "instance-list-updated-event",
 	instanceLogEvent: "instance-log-event",
 	instanceStartedEvent: "instance-started-event",
+	instanceStateChangedEvent: "instance-state-changed-event",
+	instanceStatsEvent: "instance-stats-event",
 	instanceStoppedEvent: "instance-stopped-event",
+	instanceStoppingEvent: "instance-stopping-event",
 	javaDownloadFinishedEvent: "java-download-finished-event",
 	javaDownloadProgressEvent: "java-download-progress-event",
 	javaDownloadStartedEvent: "java-download-started-event",
@@ -317,19 +623,41 @@ export const events = __makeEvents__<{
 
 /** user-defined types **/
 
-export type Account = { active: boolean; expiry: string; accessToken: string; refreshToken: string; profile: Profile };
+export type AccountKind = "microsoft" | "offline" | "yggdrasil";
+export type AccountNeedsReauthEvent = { id: string; name: string; reason: string };
+export type AccountSummary = { id: string; name: string; kind: AccountKind; skin: string | null; status: TokenStatus; active: boolean };
 export type AssetKind = "Assets" | "Libraries" | "version-jar";
 export type AssetProgressEvent = { kind: AssetKind; percentage: number };
 export type AssetsDownloadFinishedEvent = string;
 export type AssetsDownloadStartedEvent = string;
-export type Cape = { id: string; state: string; url: string; alias: string };
+export type BackupSettings = { autoBackup?: boolean; keepLast?: number | null; maxAgeDays?: number | null };
+export type Difficulty = "peaceful" | "easy" | "normal" | "hard";
+export type FileChange = { folder: InstanceFolder; kind: FileChangeKind; name: string; previousName: string | null };
+export type FileChangeKind = "created" | "modified" | "removed" | "renamed";
 export type Game = { version: string; modloader: Modloader; url: string };
+export type GameMode = "survival" | "creative" | "adventure" | "spectator";
 export type Instance = { slug: string; name: string; game: Game; java: Java; settings: Settings };
+export type InstanceFilesChangedEvent = { slug: string; changes: FileChange[] };
+export type InstanceFolder = "saves" | "mods" | "resourcePacks" | "shaderPacks" | "screenshots" | "crashReports" | "logs";
 export type InstanceListUpdatedEvent = string;
 export type InstanceLogEvent = { slug: string; line: string };
 export type InstanceStartedEvent = { slug: string; message: string };
-export type InstanceStoppedEvent = { slug: string; message: string };
-export type Java = { path: string; args: string[]; version: number };
+export type InstanceStateChangedEvent = { slug: string; state: LaunchState | null };
+export type InstanceStats = {
+	timestamp: string;
+	cpuPercent: number;
+	rssBytes: number;
+	threads: number;
+	readBytes: number;
+	writeBytes: number;
+	readBytesPerSec: number;
+	writeBytesPerSec: number;
+};
+export type InstanceStatsEvent = { slug: string; stats: InstanceStats };
+export type InstanceStoppedEvent = { slug: string; message: string; method: StopMethod };
+export type InstanceStoppingEvent = { slug: string; elapsed: number; gracePeriod: number };
+export type InventoryItem = { slot: number; id: string; count: number };
+export type Java = { path: string; args: string[]; version: number; memory?: MemorySettings | null; preset?: JvmPreset };
 export type JavaConfig = { java8Path: string; java17Path: string; java21Path: string };
 export type JavaDownloadFinishedEvent = { paths: string[] };
 export type JavaDownloadProgressEvent = { version: number; percentage: number };
@@ -338,12 +666,66 @@ export type JavaExtractFinishedEvent = { paths: string[] };
 export type JavaExtractProgressEvent = { version: number; percentage: number };
 export type JavaExtractStartedEvent = string;
 export type JavaTestInfo = { valid: boolean; version: number; vendor: string; expectedVersion: number; versionMismatch: boolean };
-export type LauncherSettings = { richPresence: boolean; useDiscreteGpu: boolean };
+export type JvmPreset = "none" | "aikar" | "zgc";
+export type LaunchEnvironment = { wrapper?: string[]; variables?: Partial<{ [key in string]: string }>; clearInherited?: boolean };
+export type LaunchError =
+	| { kind: "alreadyRunning"; message: string }
+	| { kind: "alreadyPreparing"; message: string }
+	| { kind: "downgradeRisk"; message: string }
+	| { kind: "failed"; message: string };
+export type LaunchHooks = { preLaunch?: string | null; postExit?: string | null; timeout?: number; abortOnFailure?: boolean };
+export type LaunchState = "preparing" | "downloading" | "backingUp" | "starting" | "running" | "stopping" | "finishing";
+export type LauncherSettings = {
+	richPresence: boolean;
+	useDiscreteGpu: boolean;
+	stopGracePeriod: number;
+	defaultMemory: MemorySettings;
+	launchEnvironment: LaunchEnvironment;
+};
 export type LoginDetailsEvent = { code: string; uri: string };
+export type MemorySettings = { min: number; max: number };
 export type Modloader = { loader: string; version: string | null };
-export type Profile = { id: string; name: string; skins: Skin[]; capes: Cape[] };
-export type Screenshot = { path: string; name: string; data: string };
-export type ScreenshotEvent = string;
+export type PlayerSummary = {
+	uuid: string;
+	name: string | null;
+	playTime: number;
+	deaths: number;
+	distance: number;
+	mobKills: number;
+	playerKills: number;
+	advancementsCompleted: number;
+	advancementsInProgress: number;
+	hasInventory: boolean;
+};
+export type QuickPlayEntry = { target: QuickPlayTarget; timestamp: string };
+export type QuickPlayTarget = { type: "singleplayer"; world: string } | { type: "multiplayer"; address: string };
+export type RestoreTarget = "newFolder" | "replace";
+export type Screenshot = {
+	slug: string;
+	path: string;
+	name: string;
+	url: string;
+	size: number;
+	width: number;
+	height: number;
+	createdAt: string;
+	capturedAt: string;
+	thumbnail: string | null;
+};
+export type ScreenshotEvent = { slug: string; kind: FileChangeKind; name: string; previousName: string | null };
+export type ScreenshotPage = { screenshots: Screenshot[]; total: number };
+export type Server = { name: string; ip: string; icon: string | null; acceptTextures: boolean | null };
+export type ServerStatus = {
+	motd: string;
+	version: string;
+	protocol: number | null;
+	playersOnline: number;
+	playersMax: number;
+	playerSample: string[];
+	favicon: string | null;
+	latency: number;
+	legacy: boolean;
+};
 export type Settings = {
 	hasLaunched: boolean;
 	richPresence: boolean;
@@ -352,10 +734,34 @@ export type Settings = {
 	maximized: boolean;
 	timePlayed?: number;
 	lastPlayed?: string;
+	environment?: LaunchEnvironment;
+	hooks?: LaunchHooks;
+	backups?: BackupSettings;
 };
-export type Skin = { id: string; state: string; url: string; variant: string };
+export type SpawnPosition = { x: number; y: number; z: number };
+export type StopMethod = "exited" | "terminated" | "killed";
+export type TokenStatus = "valid" | "expired" | "needsReauth" | "unknown";
+export type TrashedScreenshot = { id: string; slug: string; name: string; deletedAt: string };
 export type Version = { id: string; type: string; url: string; time: string; releaseTime: string; sha1: string; complianceLevel: number };
-export type World = { path: string; folderName: string; levelName: string; lastPlayed: string; icon: string | null };
+export type World = {
+	path: string;
+	folderName: string;
+	levelName: string;
+	lastPlayed: string;
+	icon: string | null;
+	gameMode: GameMode | null;
+	hardcore: boolean;
+	difficulty: Difficulty | null;
+	cheatsAllowed: boolean;
+	seed: string | null;
+	dataVersion: number | null;
+	versionName: string | null;
+	spawn: SpawnPosition | null;
+	dayTime: number | null;
+	size: number;
+	downgradeRisk: boolean;
+};
+export type WorldBackup = { id: string; world: string; path: string; size: number; createdAt: string };
 
 /** tauri-specta globals **/
 
@@ -3,7 +3,7 @@ import { page } from "$app/state";
 import { fetchMinecraftProfiles } from "$lib/utils/AccountUtils";
 import { authService } from "$lib/services/AuthService.svelte";
 import { resetMode, setMode } from "mode-watcher";
-import { commands, type Profile } from "$lib/bindings";
+import { commands, type AccountSummary } from "$lib/bindings";
 
 const ONBOARDING_PATHS: ReadonlyArray<string> = [
 	"#/onboarding",
@@ -20,8 +20,8 @@ export class OnboardingController {
 	selectedTheme = $state<"dark" | "light" | "system">("system");
 
 	// Account setup
-	profiles = $state<Profile[]>([]);
-	selectedProfile = $state<Profile | undefined>(undefined);
+	profiles = $state<AccountSummary[]>([]);
+	selectedProfile = $state<AccountSummary | undefined>(undefined);
 
 	// Java installation
 	javaSetupComplete = $state(false);
@@ -102,7 +102,7 @@ export class OnboardingController {
 		authService.cancelLoginPopup();
 	}
 
-	selectProfile(profile: Profile) {
+	selectProfile(profile: AccountSummary) {
 		this.selectedProfile = profile;
 	}
 
@@ -2,13 +2,13 @@ import { goto } from "$app/navigation";
 import { fetchMinecraftProfiles } from "$lib/utils/AccountUtils";
 import { authService } from "$lib/services/AuthService.svelte";
 import { type UnlistenFn } from "@tauri-apps/api/event";
-import { commands, events, type Instance, type Profile } from "$lib/bindings";
+import { commands, events, type AccountSummary, type Instance } from "$lib/bindings";
 
 export class SidebarController {
 	instances = $state<Instance[]>([]);
 	searchInput = $state("");
-	profiles = $state<Profile[]>([]);
-	selectedProfile = $state<Profile | undefined>(undefined);
+	profiles = $state<AccountSummary[]>([]);
+	selectedProfile = $state<AccountSummary | undefined>(undefined);
 
 	filteredInstances = $derived(() => {
 		if (this.searchInput.trim() === "") {
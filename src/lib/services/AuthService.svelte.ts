@@ -1,5 +1,5 @@
 import { type UnlistenFn } from "@tauri-apps/api/event";
-import { commands, events, type Profile } from "$lib/bindings";
+import { commands, events, type AccountSummary } from "$lib/bindings";
 
 class AuthService {
 	loginCode = $state("");
@@ -30,10 +30,10 @@ class AuthService {
 	 * Initiates the login process.
 	 * The "login" Tauri command is expected to first emit "login-details"
 	 * (which shows the popup via the listener in init) and then resolve
-	 * with the MinecraftProfile upon successful authentication.
-	 * @returns A Promise that resolves with the MinecraftProfile if login is successful, otherwise null.
+	 * with the added account upon successful authentication.
+	 * @returns A Promise that resolves with the added account if login is successful, otherwise null.
 	 */
-	async startLogin(): Promise<Profile | null> {
+	async startLogin(): Promise<AccountSummary | null> {
 		if (!this.isInitialized) {
 			await this.init();
 		}
@@ -1,10 +1,10 @@
-import { commands, type Account, type Profile } from "$lib/bindings";
+import { commands, type AccountSummary } from "$lib/bindings";
 
 /**
  * Fetches the list of Minecraft profiles from the backend.
  * @returns A promise that resolves to an array of Minecraft profiles.
  */
-export async function fetchMinecraftProfiles(): Promise<Profile[]> {
+export async function fetchMinecraftProfiles(): Promise<AccountSummary[]> {
 	const res = await commands.getMinecraftProfiles();
 	if (res.status === "ok") {
 		return res.data;
@@ -18,7 +18,7 @@ export async function fetchMinecraftProfiles(): Promise<Profile[]> {
  * Fetches the currently active account.
  * @returns A promise that resolves to the active account or null if none is active.
  */
-export async function getActiveAccount(): Promise<Account | null> {
+export async function getActiveAccount(): Promise<AccountSummary | null> {
 	const res = await commands.getActiveAccount();
 	if (res.status === "ok") {
 		return res.data;